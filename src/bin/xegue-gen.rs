@@ -0,0 +1,246 @@
+use std::env;
+use std::process::ExitCode;
+
+use xegue::dungeon_level::dungeon_level::{generate_dungeon_level_with, DungeonLevel};
+use xegue::dungeon_level::level_stats::{analyze_generation, GenerationReport};
+use xegue::dungeon_level::level_validation::validate_level;
+use xegue::dungeon_level::terrain_generator::TerrainGenerator;
+use xegue::export::html::level_to_html;
+use xegue::export::overlay::Overlays;
+use xegue::export::svg::level_to_svg;
+use xegue::rng::GameRng;
+use xegue::terminal::draw_terrain::terrain_to_lines;
+use xegue::thing::knowledge::Knowledge;
+use xegue::thing::new_thing::ThingCounters;
+
+const USAGE: &str = "\
+usage: xegue-gen [options]
+
+Generate dungeon levels and print them to stdout.
+
+options:
+  --seed N          seed of the first level (default: random)
+  --depth N         dungeon depth to generate for (default: 1)
+  --count N         number of levels; level i uses seed N+i (default: 1)
+  --format FORMAT   text, json, svg or html (default: text); svg and
+                    html take a single level
+  --overlay LIST    comma-separated overlays for svg and html: indices,
+                    bounds, passages, doors, gone, or all
+  --validate        check each level instead of printing it; exits with
+                    status 1 if any level fails
+  --stats           print statistics over all the levels instead of the
+                    levels themselves
+  -h, --help        show this message";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Svg,
+    Html,
+}
+
+struct Options {
+    seed: Option<u32>,
+    depth: i32,
+    count: u32,
+    format: Format,
+    overlays: Overlays,
+    validate: bool,
+    stats: bool,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        seed: None,
+        depth: 1,
+        count: 1,
+        format: Format::Text,
+        overlays: Overlays::default(),
+        validate: false,
+        stats: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        // Accept both `--seed 5` and `--seed=5`.
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match name.as_str() {
+            "--seed" => options.seed = Some(parse_number(&name, &value()?)?),
+            "--depth" => options.depth = parse_number(&name, &value()?)?,
+            "--count" => options.count = parse_number(&name, &value()?)?,
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "svg" => Format::Svg,
+                    "html" => Format::Html,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--overlay" => options.overlays = parse_overlays(&value()?)?,
+            "--validate" => options.validate = true,
+            "--stats" => options.stats = true,
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown option '{}'", name)),
+        }
+    }
+
+    if options.depth < 1 {
+        return Err("--depth must be at least 1".into());
+    }
+    if options.validate && options.stats {
+        return Err("--validate and --stats cannot be combined".into());
+    }
+    let drawing = matches!(options.format, Format::Svg | Format::Html);
+    if drawing && options.count != 1 && !options.validate && !options.stats {
+        return Err("svg and html output take a single level; use --count 1".into());
+    }
+    Ok(Some(options))
+}
+
+fn parse_overlays(list: &str) -> Result<Overlays, String> {
+    let mut overlays = Overlays::default();
+    for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match name {
+            "indices" => overlays.room_indices = true,
+            "bounds" => overlays.room_bounds = true,
+            "passages" => overlays.passages = true,
+            "doors" => overlays.doors = true,
+            "gone" => overlays.gone_rooms = true,
+            "all" => overlays = Overlays::all(),
+            other => return Err(format!("unknown overlay '{}'", other)),
+        }
+    }
+    Ok(overlays)
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", name, value))
+}
+
+fn generator(depth: i32) -> TerrainGenerator {
+    TerrainGenerator {
+        depth,
+        ..TerrainGenerator::default()
+    }
+}
+
+fn generate(seed: u32, depth: i32) -> DungeonLevel {
+    // Fresh counters per level so every seed reproduces on its own.
+    let mut counters = ThingCounters::default();
+    let knowledge = Knowledge::default();
+    let mut rng = GameRng::new(seed);
+    generate_dungeon_level_with(&generator(depth), &mut counters, &knowledge, &mut rng)
+}
+
+#[cfg(feature = "serde")]
+fn print_json(seed: u32, depth: i32, level: &DungeonLevel) -> Result<(), String> {
+    use xegue::dungeon_level::level_json::level_to_json_value;
+
+    let document = level_to_json_value(level).map_err(|err| err.to_string())?;
+    let line = serde_json::json!({ "seed": seed, "depth": depth, "document": document });
+    println!("{}", line);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_json(_seed: u32, _depth: i32, _level: &DungeonLevel) -> Result<(), String> {
+    Err("JSON output needs xegue built with the `serde` feature".into())
+}
+
+#[cfg(feature = "serde")]
+fn print_report_json(report: &GenerationReport) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report).map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_report_json(_report: &GenerationReport) -> Result<(), String> {
+    Err("JSON output needs xegue built with the `serde` feature".into())
+}
+
+fn run(options: &Options) -> Result<bool, String> {
+    let first = options
+        .seed
+        .unwrap_or_else(|| GameRng::from_entropy().state());
+    if options.stats {
+        let report = analyze_generation(&generator(options.depth), first, options.count);
+        match options.format {
+            Format::Json => print_report_json(&report)?,
+            _ => print!("{}", report),
+        }
+        return Ok(true);
+    }
+
+    let mut all_valid = true;
+
+    for i in 0..options.count {
+        let seed = first.wrapping_add(i);
+        let level = generate(seed, options.depth);
+
+        if options.validate {
+            let issues = validate_level(&level);
+            if issues.is_empty() {
+                println!("seed {}: ok", seed);
+            } else {
+                all_valid = false;
+                println!("seed {}: {} problem(s)", seed, issues.len());
+                for issue in issues {
+                    println!("  {}", issue);
+                }
+            }
+            continue;
+        }
+
+        match options.format {
+            Format::Text => {
+                println!("# seed {} depth {}", seed, options.depth);
+                for line in terrain_to_lines(&level.terrain) {
+                    println!("{}", line);
+                }
+                println!();
+            }
+            Format::Json => print_json(seed, options.depth, &level)?,
+            Format::Svg => print!("{}", level_to_svg(&level, &options.overlays)),
+            Format::Html => print!("{}", level_to_html(&level, &options.overlays)),
+        }
+    }
+
+    Ok(all_valid)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("xegue-gen: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("xegue-gen: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use rand::Rng;
+
+/// Pick a very random number in `0..range` (Rogue's `rnd`).
+///
+/// A non-positive range yields 0 rather than panicking.
+pub fn rnd<R: Rng + ?Sized>(rng: &mut R, range: i32) -> i32 {
+    if range <= 0 {
+        0
+    } else {
+        rng.random_range(0..range)
+    }
+}
+
+/// Roll `number` dice with `sides` sides each and sum them (Rogue's `roll`).
+pub fn roll<R: Rng + ?Sized>(rng: &mut R, number: i32, sides: i32) -> i32 {
+    (0..number.max(0)).map(|_| rnd(rng, sides) + 1).sum()
+}
+
+/// Give a spread around a given number: +/- 10% (Rogue's `spread`).
+pub fn spread<R: Rng + ?Sized>(rng: &mut R, nm: i32) -> i32 {
+    nm - nm / 20 + rnd(rng, nm / 10)
+}
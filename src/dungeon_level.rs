@@ -1,2 +1,10 @@
 pub mod terrain_generator;
-pub mod dungeon_level;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod dungeon_level;
+pub mod treasure_room;
+pub mod dungeon;
+pub mod level_parser;
+pub mod level_validation;
+pub mod level_stats;
+#[cfg(feature = "serde")]
+pub mod level_json;
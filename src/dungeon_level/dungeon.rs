@@ -0,0 +1,52 @@
+use rand::Rng;
+
+use crate::dungeon_level::dungeon_level::{generate_dungeon_level_with, DungeonLevel};
+use crate::dungeon_level::terrain_generator::TerrainGenerator;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::new_thing::ThingCounters;
+
+/// The whole dungeon as far as the player is concerned: the level they are
+/// on and how deep they are.
+pub struct Dungeon {
+    /// Current dungeon level (Rogue's `level`).
+    pub depth: i32,
+    /// Deepest level reached so far (Rogue's `max_level`).
+    pub max_depth: i32,
+    /// The level the player is currently on.
+    pub level: DungeonLevel,
+}
+
+impl Dungeon {
+    /// Start a new dungeon with its first level.
+    pub fn new<R: Rng + ?Sized>(
+        counters: &mut ThingCounters,
+        knowledge: &Knowledge,
+        rng: &mut R,
+    ) -> Self {
+        let generator = TerrainGenerator::default();
+        let level = generate_dungeon_level_with(&generator, counters, knowledge, rng);
+        Self {
+            depth: generator.depth,
+            max_depth: generator.depth,
+            level,
+        }
+    }
+
+    /// Replace the current level with a freshly generated one at `depth`
+    /// (Rogue's `new_level`).
+    pub fn new_level<R: Rng + ?Sized>(
+        &mut self,
+        depth: i32,
+        counters: &mut ThingCounters,
+        knowledge: &Knowledge,
+        rng: &mut R,
+    ) {
+        let generator = TerrainGenerator {
+            depth,
+            ..TerrainGenerator::default()
+        };
+        self.depth = depth;
+        self.max_depth = self.max_depth.max(depth);
+        self.level = generate_dungeon_level_with(&generator, counters, knowledge, rng);
+    }
+}
@@ -1,38 +1,205 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::terrain::terrain_cell::TerrainCellClass;
 use crate::terrain::terrain_grid::TerrainGrid;
-use crate::terrain_structure::room::Room;
+use crate::terrain_structure::room::{room_contains, Room};
 use crate::terrain_structure::passage::Passage;
 use crate::dungeon_level::terrain_generator::TerrainGenerator;
+use crate::dungeon_level::treasure_room::put_treasure_room;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::new_thing::ThingCounters;
+use crate::thing::{MonsterThing, ObjectThing};
+
+/// Level at which the Amulet of Yendor is found.
+pub const AMULET_LEVEL: i32 = 26;
 
+/// Number of tries `find_floor` makes before giving up.
+pub const MAX_TRIES: u32 = 10;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DungeonLevel {
     pub terrain: TerrainGrid,
     pub rooms: Vec<Room>,
     pub passages: Vec<Passage>,
+    /// Objects lying on the floor of this level.
+    pub objects: Vec<ObjectThing>,
+    /// Monsters on this level.
+    pub monsters: Vec<MonsterThing>,
+}
+
+impl DungeonLevel {
+    /// Get the cell class at a position, if it is inside the terrain.
+    pub fn cell_class_at(&self, pos: IVec2) -> Option<TerrainCellClass> {
+        if pos.x < 0 || pos.y < 0 {
+            return None;
+        }
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        if x >= self.terrain.width || y >= self.terrain.height {
+            return None;
+        }
+        self.terrain.get(x, y).map(|cell| cell.cell_class)
+    }
+
+    /// Find the monster standing at a position, if any (Rogue's `moat`).
+    pub fn monster_at(&self, pos: IVec2) -> Option<&MonsterThing> {
+        self.monsters.iter().find(|m| m.pos == pos)
+    }
+
+    /// Find the index of the room a position is in (Rogue's `roomin`).
+    ///
+    /// Gone rooms are never returned.
+    pub fn room_at(&self, pos: IVec2) -> Option<usize> {
+        self.rooms
+            .iter()
+            .position(|room| !room.is_gone && room_contains(room, pos))
+    }
+
+    /// Whether a position lies in a dark room or a maze, where only what
+    /// is next to the hero can be seen.
+    pub fn is_dark_at(&self, pos: IVec2) -> bool {
+        self.room_at(pos)
+            .is_some_and(|r| self.rooms[r].is_dark || self.rooms[r].is_maze)
+    }
+
+    /// Pick a random room that is really there (Rogue's `rnd_room`).
+    pub fn rnd_room<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        let candidates: Vec<usize> = (0..self.rooms.len())
+            .filter(|&i| !self.rooms[i].is_gone && !self.rooms[i].is_maze)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates[rnd(rng, candidates.len() as i32) as usize])
+    }
+
+    /// Find a valid floor spot in this room. If `room_id` is `None`, pick a
+    /// new room each time around the loop (Rogue's `find_floor`).
+    ///
+    /// With `monst` set the spot only has to be walkable and free of other
+    /// monsters; otherwise it must be bare floor. A `limit` of 0 keeps trying
+    /// forever.
+    pub fn find_floor<R: Rng + ?Sized>(
+        &self,
+        room_id: Option<usize>,
+        limit: u32,
+        monst: bool,
+        rng: &mut R,
+    ) -> Option<IVec2> {
+        let mut cnt = limit;
+        loop {
+            if limit != 0 {
+                if cnt == 0 {
+                    return None;
+                }
+                cnt -= 1;
+            }
+
+            let room = match room_id {
+                Some(id) => &self.rooms[id],
+                None => &self.rooms[self.rnd_room(rng)?],
+            };
+            if room.size.x <= 2 || room.size.y <= 2 {
+                return None;
+            }
+            let comp = if room.is_maze {
+                TerrainCellClass::Passage
+            } else {
+                TerrainCellClass::Floor
+            };
+
+            let pos = IVec2::new(
+                room.pos.x + rnd(rng, room.size.x - 2) + 1,
+                room.pos.y + rnd(rng, room.size.y - 2) + 1,
+            );
+            let Some(class) = self.cell_class_at(pos) else {
+                continue;
+            };
+            if monst {
+                if self.monster_at(pos).is_none() && class.is_walkable() {
+                    return Some(pos);
+                }
+            } else if class == comp {
+                return Some(pos);
+            }
+        }
+    }
 }
 
 // Create a DungeonLevel, generate rooms, place them on the terrain,
 // and carve doors & passages based on the generated adjacency graph.
 pub fn generate_dungeon_level() -> DungeonLevel {
     let generator = TerrainGenerator::default();
+    let mut counters = ThingCounters::default();
+    let knowledge = Knowledge::default();
+    generate_dungeon_level_with(&generator, &mut counters, &knowledge, &mut rand::rng())
+}
 
+/// Generate a level with the given generator settings and random number
+/// generator, so that the same seed always gives the same level.
+pub fn generate_dungeon_level_with<R: Rng + ?Sized>(
+    generator: &TerrainGenerator,
+    counters: &mut ThingCounters,
+    knowledge: &Knowledge,
+    rng: &mut R,
+) -> DungeonLevel {
     let mut terrain = TerrainGrid::init(
         generator.terrain_size.x as usize,
         generator.terrain_size.y as usize,
     );
 
     // Rooms must be mutable because carving will push door positions into them.
-    let mut rooms = generator.generate_rooms();
+    let mut rooms = generator.generate_rooms(rng);
 
     // First, put rooms on the terrain.
     generator.put_rooms_on_grid(&mut terrain, rooms.clone());
 
     // Then generate a random connection graph and carve passages.
-    let adjacency = generator.generate_room_connections();
-    let passages = generator.carve_passages(&mut terrain, &mut rooms, &adjacency);
+    let adjacency = generator.generate_room_connections(rng);
+    let passages = generator.carve_passages(&mut terrain, &mut rooms, &adjacency, rng);
 
-    DungeonLevel {
+    let mut level = DungeonLevel {
         terrain,
         rooms,
         passages,
-    }
+        objects: Vec::new(),
+        monsters: Vec::new(),
+    };
+
+    // Another level without food so far (Rogue's `no_food++`).
+    counters.no_food += 1;
+
+    // Finally, maybe turn one of the rooms into a treasure vault.
+    put_treasure_room(
+        &mut level,
+        generator.depth,
+        generator.treasure_room_chance,
+        counters,
+        knowledge,
+        rng,
+    );
+
+    level
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+
+    #[test]
+    fn each_level_counts_toward_food() {
+        let generator = TerrainGenerator {
+            treasure_room_chance: 0,
+            ..TerrainGenerator::default()
+        };
+        let mut counters = ThingCounters::default();
+        let knowledge = Knowledge::default();
+        let mut rng = GameRng::new(1);
+        for levels in 1..=4 {
+            generate_dungeon_level_with(&generator, &mut counters, &knowledge, &mut rng);
+            assert_eq!(counters.no_food, levels);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+
+/// Version of the JSON level document. Bump it whenever a change to the
+/// serialized types would break readers of older documents.
+pub const LEVEL_JSON_VERSION: u32 = 1;
+
+/// The JSON document external tools read: a version tag and the level.
+///
+/// Positions (`IVec2`) are written as `[x, y]` arrays, terrain cells as a
+/// flat row-major `cells` array of `width * height` entries, and enum
+/// variants by their Rust names.
+#[derive(Serialize, Deserialize)]
+pub struct LevelDocument {
+    pub version: u32,
+    pub level: DungeonLevel,
+}
+
+#[derive(Serialize)]
+struct LevelDocumentRef<'a> {
+    version: u32,
+    level: &'a DungeonLevel,
+}
+
+/// Errors from reading a JSON level document.
+#[derive(Debug)]
+pub enum LevelJsonError {
+    Json(serde_json::Error),
+    /// The document was written by an incompatible version.
+    Version {
+        found: u32,
+        expected: u32,
+    },
+}
+
+impl std::fmt::Display for LevelJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelJsonError::Json(err) => write!(f, "invalid level JSON: {}", err),
+            LevelJsonError::Version { found, expected } => write!(
+                f,
+                "level JSON version {} is not supported (expected {})",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LevelJsonError {}
+
+impl From<serde_json::Error> for LevelJsonError {
+    fn from(err: serde_json::Error) -> Self {
+        LevelJsonError::Json(err)
+    }
+}
+
+/// Write a level as a pretty-printed, versioned JSON document.
+pub fn level_to_json(level: &DungeonLevel) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&LevelDocumentRef {
+        version: LEVEL_JSON_VERSION,
+        level,
+    })
+}
+
+/// The same document as `level_to_json`, as a JSON value, for tools that
+/// embed levels in larger documents.
+pub fn level_to_json_value(level: &DungeonLevel) -> Result<serde_json::Value, serde_json::Error> {
+    serde_json::to_value(LevelDocumentRef {
+        version: LEVEL_JSON_VERSION,
+        level,
+    })
+}
+
+/// Read a level back from a JSON document written by `level_to_json`.
+pub fn level_from_json(json: &str) -> Result<DungeonLevel, LevelJsonError> {
+    let doc: LevelDocument = serde_json::from_str(json)?;
+    if doc.version != LEVEL_JSON_VERSION {
+        return Err(LevelJsonError::Version {
+            found: doc.version,
+            expected: LEVEL_JSON_VERSION,
+        });
+    }
+    Ok(doc.level)
+}
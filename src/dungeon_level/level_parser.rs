@@ -0,0 +1,364 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::terminal::parse_terrain::{
+    lines_to_legend_entries, LegendEntry, MapLegend, MapParseError,
+};
+use crate::terrain::terrain_cell::{TerrainCellClass, F_PASS, F_SECRET};
+use crate::terrain::terrain_grid::TerrainGrid;
+use crate::terrain_structure::room::{Room, RoomKind};
+use crate::thing::monster_table::new_monster;
+use crate::thing::object_table::object_cell_class;
+use crate::thing::{ObjectThing, AMULET, ARMOR, FOOD, GOLD, POTION, RING, SCROLL, STICK, WEAPON};
+
+/// A level read from a text map, along with where the player starts.
+pub struct ParsedLevel {
+    pub level: DungeonLevel,
+    pub player: Option<IVec2>,
+}
+
+/// Build a full `DungeonLevel` from a text map.
+///
+/// Rooms are found as areas of floor fully enclosed by walls and doors, so
+/// gone rooms cannot be expressed and room indices follow reading order
+/// rather than Rogue's 3x3 grid. Doors (hidden ones included) on a room's
+/// walls become its exits. Objects get their type's first variant, and
+/// monsters are rolled for `depth` with `rng`. No passages are recorded.
+/// Hidden doors and passages are kept as Rogue keeps them: wall or blank
+/// rock marked `F_SECRET`, which `search` turns into a door or passage.
+pub fn parse_dungeon_level<I, S, R>(
+    lines: I,
+    legend: &MapLegend,
+    depth: i32,
+    rng: &mut R,
+) -> Result<ParsedLevel, MapParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    R: Rng + ?Sized,
+{
+    let (entries, width, height) = lines_to_legend_entries(lines, legend)?;
+
+    let mut terrain = TerrainGrid::init(width, height);
+    let mut player = None;
+    let mut monsters = Vec::new();
+    let mut marks = Vec::new();
+
+    // First pass: lay down the terrain, treating anything standing on the
+    // map as floor until the rooms are known.
+    for (i, entry) in entries.iter().enumerate() {
+        let (x, y) = (i % width, i / width);
+        let pos = IVec2::new(x as i32, y as i32);
+        let (class, flags) = match *entry {
+            LegendEntry::Terrain(class) => (class, 0),
+            LegendEntry::Player => {
+                if player.is_some() {
+                    return Err(MapParseError::MultiplePlayers { x, y });
+                }
+                player = Some(pos);
+                (TerrainCellClass::Floor, 0)
+            }
+            LegendEntry::Monster(kind) => {
+                if !kind.is_ascii_uppercase() {
+                    return Err(MapParseError::UnknownMonster { kind, x, y });
+                }
+                monsters.push((kind, pos));
+                (TerrainCellClass::Floor, 0)
+            }
+            LegendEntry::HiddenDoor => (TerrainCellClass::Door, F_SECRET),
+            LegendEntry::HiddenPassage => (TerrainCellClass::Empty, F_PASS | F_SECRET),
+            LegendEntry::DarkRoom | LegendEntry::TreasureRoom => {
+                marks.push((*entry, pos));
+                (TerrainCellClass::Floor, 0)
+            }
+        };
+        if let Some(cell) = terrain.get_mut(x, y) {
+            cell.cell_class = class;
+            cell.flags = flags;
+        }
+    }
+
+    let mut rooms = find_rooms(&terrain);
+
+    // Anything standing outside a room is standing in a passage.
+    for &pos in player.iter().chain(monsters.iter().map(|(_, pos)| pos)) {
+        if room_with_floor_at(&rooms, pos).is_none() {
+            set_class(&mut terrain, pos, TerrainCellClass::Passage);
+        }
+    }
+    for (mark, pos) in marks {
+        let Some(id) = room_with_floor_at(&rooms, pos) else {
+            set_class(&mut terrain, pos, TerrainCellClass::Passage);
+            continue;
+        };
+        match mark {
+            LegendEntry::DarkRoom => rooms[id].is_dark = true,
+            _ => rooms[id].kind = RoomKind::Treasure,
+        }
+    }
+
+    // Hidden doors look like the wall they sit in.
+    for y in 0..height {
+        for x in 0..width {
+            let Some(cell) = terrain.get(x, y) else {
+                continue;
+            };
+            if cell.cell_class != TerrainCellClass::Door || cell.flags & F_SECRET == 0 {
+                continue;
+            }
+            let is_horizontal_wall = |nx: usize| {
+                nx < width
+                    && terrain
+                        .get(nx, y)
+                        .is_some_and(|c| c.cell_class == TerrainCellClass::WallHorizontal)
+            };
+            let horizontal = (x > 0 && is_horizontal_wall(x - 1)) || is_horizontal_wall(x + 1);
+            let wall = if horizontal {
+                TerrainCellClass::WallHorizontal
+            } else {
+                TerrainCellClass::WallVertical
+            };
+            set_class(&mut terrain, IVec2::new(x as i32, y as i32), wall);
+        }
+    }
+
+    let mut objects = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let class = terrain.get(x, y).map(|c| c.cell_class);
+            if let Some(kind) = class.and_then(object_kind) {
+                let mut obj = ObjectThing::new(kind, 0);
+                obj.pos = IVec2::new(x as i32, y as i32);
+                objects.push(obj);
+            }
+        }
+    }
+
+    let mut level = DungeonLevel {
+        terrain,
+        rooms,
+        passages: Vec::new(),
+        objects,
+        monsters: Vec::new(),
+    };
+    for (kind, pos) in monsters {
+        let mut tp = new_monster(kind, pos, depth, rng);
+        tp.room_id = level.room_at(pos);
+        level.monsters.push(tp);
+    }
+
+    Ok(ParsedLevel { level, player })
+}
+
+/// The object type shown by a terrain cell class, if it shows one.
+fn object_kind(class: TerrainCellClass) -> Option<i32> {
+    [
+        POTION, SCROLL, FOOD, WEAPON, ARMOR, RING, STICK, GOLD, AMULET,
+    ]
+    .into_iter()
+    .find(|&kind| object_cell_class(kind) == class)
+}
+
+fn set_class(terrain: &mut TerrainGrid, pos: IVec2, class: TerrainCellClass) {
+    if let Some(cell) = terrain.get_mut(pos.x as usize, pos.y as usize) {
+        cell.cell_class = class;
+    }
+}
+
+/// The room whose floor (walls excluded) covers a position.
+fn room_with_floor_at(rooms: &[Room], pos: IVec2) -> Option<usize> {
+    rooms.iter().position(|room| {
+        pos.x > room.pos.x
+            && pos.y > room.pos.y
+            && pos.x < room.pos.x + room.size.x - 1
+            && pos.y < room.pos.y + room.size.y - 1
+    })
+}
+
+fn is_room_inside(class: TerrainCellClass) -> bool {
+    class.is_walkable() && class != TerrainCellClass::Passage && class != TerrainCellClass::Door
+}
+
+fn is_room_boundary(class: TerrainCellClass) -> bool {
+    matches!(
+        class,
+        TerrainCellClass::WallHorizontal | TerrainCellClass::WallVertical | TerrainCellClass::Door
+    )
+}
+
+/// Find the rooms on a grid: each connected patch of floor whose bounding
+/// box is ringed by walls and doors. Doors on the ring become exits.
+fn find_rooms(terrain: &TerrainGrid) -> Vec<Room> {
+    let (width, height) = (terrain.width, terrain.height);
+    let class_at = |x: i32, y: i32| -> Option<TerrainCellClass> {
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return None;
+        }
+        terrain.get(x as usize, y as usize).map(|c| c.cell_class)
+    };
+
+    let mut visited = vec![false; width * height];
+    let mut rooms = Vec::new();
+
+    for start in 0..width * height {
+        let (sx, sy) = ((start % width) as i32, (start / width) as i32);
+        if visited[start] || !class_at(sx, sy).is_some_and(is_room_inside) {
+            continue;
+        }
+
+        // Flood fill the patch and track its bounding box.
+        let (mut min, mut max) = (IVec2::new(sx, sy), IVec2::new(sx, sy));
+        let mut stack = vec![IVec2::new(sx, sy)];
+        visited[start] = true;
+        while let Some(p) = stack.pop() {
+            min = min.min(p);
+            max = max.max(p);
+            for d in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                let n = p + d;
+                if !class_at(n.x, n.y).is_some_and(is_room_inside) {
+                    continue;
+                }
+                let idx = n.y as usize * width + n.x as usize;
+                if !visited[idx] {
+                    visited[idx] = true;
+                    stack.push(n);
+                }
+            }
+        }
+
+        // The ring around the patch must be solid wall or door.
+        let pos = min - IVec2::ONE;
+        let size = max - min + IVec2::splat(3);
+        let mut ring = Vec::new();
+        for x in pos.x..pos.x + size.x {
+            ring.push(IVec2::new(x, pos.y));
+            ring.push(IVec2::new(x, pos.y + size.y - 1));
+        }
+        for y in pos.y + 1..pos.y + size.y - 1 {
+            ring.push(IVec2::new(pos.x, y));
+            ring.push(IVec2::new(pos.x + size.x - 1, y));
+        }
+        if !ring
+            .iter()
+            .all(|p| class_at(p.x, p.y).is_some_and(is_room_boundary))
+        {
+            continue;
+        }
+
+        let doors = ring
+            .into_iter()
+            .filter(|p| class_at(p.x, p.y) == Some(TerrainCellClass::Door))
+            .collect();
+        rooms.push(Room {
+            pos,
+            size,
+            is_maze: false,
+            is_gone: false,
+            is_dark: false,
+            doors,
+            kind: RoomKind::Normal,
+        });
+    }
+
+    rooms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+
+    const MAP: [&str; 7] = [
+        "------        ",
+        "|.@..+####    ",
+        "|..!.|   #    ",
+        "------   #    ",
+        "       --&---~",
+        "       |.B..| ",
+        "       ------ ",
+    ];
+
+    fn parse(lines: &[&str], legend: &MapLegend) -> Result<ParsedLevel, MapParseError> {
+        parse_dungeon_level(lines.iter(), legend, 1, &mut GameRng::new(1))
+    }
+
+    #[test]
+    fn finds_rooms_doors_and_things() {
+        let parsed = parse(&MAP, &MapLegend::default()).unwrap();
+        let level = &parsed.level;
+        assert_eq!(parsed.player, Some(IVec2::new(2, 1)));
+
+        assert_eq!(level.rooms.len(), 2);
+        assert_eq!(level.rooms[0].pos, IVec2::new(0, 0));
+        assert_eq!(level.rooms[0].size, IVec2::new(6, 4));
+        assert_eq!(level.rooms[0].doors, vec![IVec2::new(5, 1)]);
+        assert_eq!(level.rooms[1].doors, vec![IVec2::new(9, 4)]);
+
+        assert_eq!(level.objects.len(), 1);
+        assert_eq!(level.objects[0].kind, POTION);
+        assert_eq!(level.monsters.len(), 1);
+        assert_eq!(level.monsters[0].kind, 'B');
+        assert_eq!(level.monsters[0].room_id, Some(1));
+        assert_eq!(
+            level.cell_class_at(IVec2::new(9, 5)),
+            Some(TerrainCellClass::Floor)
+        );
+    }
+
+    #[test]
+    fn keeps_hidden_doors_and_passages_as_rogue_does() {
+        let level = parse(&MAP, &MapLegend::default()).unwrap().level;
+        let door = level.terrain.get(9, 4).unwrap();
+        assert_eq!(door.cell_class, TerrainCellClass::WallHorizontal);
+        assert_eq!(door.flags, F_SECRET);
+        let passage = level.terrain.get(13, 4).unwrap();
+        assert_eq!(passage.cell_class, TerrainCellClass::Empty);
+        assert_eq!(passage.flags, F_PASS | F_SECRET);
+    }
+
+    #[test]
+    fn marks_dark_and_treasure_rooms() {
+        let map = ["-----  -----", "|.d.|  |.t.|", "-----  -----"];
+        let level = parse(&map, &MapLegend::default()).unwrap().level;
+        assert!(level.rooms[0].is_dark);
+        assert_eq!(level.rooms[0].kind, RoomKind::Normal);
+        assert!(!level.rooms[1].is_dark);
+        assert_eq!(level.rooms[1].kind, RoomKind::Treasure);
+    }
+
+    #[test]
+    fn floor_without_walls_is_not_a_room() {
+        let level = parse(&["....", "|..|"], &MapLegend::default())
+            .unwrap()
+            .level;
+        assert!(level.rooms.is_empty());
+    }
+
+    #[test]
+    fn reports_legend_errors() {
+        let legend = MapLegend::default();
+        assert_eq!(parse(&[""], &legend).err(), Some(MapParseError::Empty));
+        assert_eq!(
+            parse(&["|.x|"], &legend).err(),
+            Some(MapParseError::UnknownSymbol {
+                ch: 'x',
+                x: 2,
+                y: 0
+            })
+        );
+        assert_eq!(
+            parse(&["@.@"], &legend).err(),
+            Some(MapParseError::MultiplePlayers { x: 2, y: 0 })
+        );
+        let legend = legend.with('m', LegendEntry::Monster('m'));
+        assert_eq!(
+            parse(&[".m"], &legend).err(),
+            Some(MapParseError::UnknownMonster {
+                kind: 'm',
+                x: 1,
+                y: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon_level::{generate_dungeon_level_with, DungeonLevel};
+use crate::dungeon_level::terrain_generator::TerrainGenerator;
+use crate::rng::GameRng;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::terrain_structure::room_grid::RoomAdjacency;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::new_thing::ThingCounters;
+
+/// Measurements taken from one generated level.
+#[derive(Debug, Clone)]
+pub struct LevelStats {
+    /// Rooms that were actually built.
+    pub room_count: usize,
+    /// Rooms left out of the level (Rogue's `ISGONE`).
+    pub gone_rooms: usize,
+    /// Floor area of each built room, walls excluded.
+    pub room_areas: Vec<i32>,
+    /// Length in tiles of each passage, doors included.
+    pub corridor_lengths: Vec<usize>,
+    /// Passage cells with only one way out.
+    pub dead_ends: usize,
+    /// Connections beyond the ones needed to join every room.
+    pub extra_edges: usize,
+}
+
+impl LevelStats {
+    /// Measure a level.
+    pub fn of(level: &DungeonLevel) -> Self {
+        let built = level.rooms.iter().filter(|r| !r.is_gone);
+
+        // Rebuild the realized connection graph from the carved passages.
+        let mut adjacency = RoomAdjacency::new();
+        for passage in &level.passages {
+            adjacency.connect(passage.room_a, passage.room_b);
+        }
+
+        let walkable = |pos: IVec2| level.cell_class_at(pos).is_some_and(|c| c.is_walkable());
+        let mut dead_ends = 0;
+        for y in 0..level.terrain.height as i32 {
+            for x in 0..level.terrain.width as i32 {
+                let pos = IVec2::new(x, y);
+                if level.cell_class_at(pos) != Some(TerrainCellClass::Passage) {
+                    continue;
+                }
+                let exits = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+                    .into_iter()
+                    .filter(|&d| walkable(pos + d))
+                    .count();
+                if exits <= 1 {
+                    dead_ends += 1;
+                }
+            }
+        }
+
+        LevelStats {
+            room_count: built.clone().count(),
+            gone_rooms: level.rooms.iter().filter(|r| r.is_gone).count(),
+            room_areas: built
+                .map(|r| (r.size.x - 2).max(0) * (r.size.y - 2).max(0))
+                .collect(),
+            corridor_lengths: level.passages.iter().map(|p| p.tiles.len()).collect(),
+            dead_ends,
+            extra_edges: adjacency
+                .edge_count()
+                .saturating_sub(level.rooms.len().saturating_sub(1)),
+        }
+    }
+}
+
+/// Summary of a set of samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Distribution {
+    pub samples: usize,
+    pub min: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub p90: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    /// Summarize samples; all fields are zero when there are none.
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Distribution::default();
+        }
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len();
+        // Nearest-rank percentile.
+        let rank = |p: f64| samples[((p * n as f64).ceil() as usize).clamp(1, n) - 1];
+        Distribution {
+            samples: n,
+            min: samples[0],
+            mean: samples.iter().sum::<f64>() / n as f64,
+            median: rank(0.5),
+            p90: rank(0.9),
+            max: samples[n - 1],
+        }
+    }
+}
+
+/// Aggregate statistics of a generator over a run of seeds.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationReport {
+    pub first_seed: u32,
+    pub levels: u32,
+    pub depth: i32,
+    /// Built rooms per level.
+    pub room_count: Distribution,
+    /// Gone rooms per level.
+    pub gone_rooms: Distribution,
+    /// Floor area per built room.
+    pub room_area: Distribution,
+    /// Tiles per passage.
+    pub corridor_length: Distribution,
+    /// Dead-end passage cells per level.
+    pub dead_ends: Distribution,
+    /// Extra connections per level.
+    pub extra_edges: Distribution,
+    /// Time to generate one level, in microseconds.
+    pub generation_micros: Distribution,
+}
+
+/// Generate `levels` levels with `generator`, level `i` seeded with
+/// `first_seed + i`, and summarize what came out.
+pub fn analyze_generation(
+    generator: &TerrainGenerator,
+    first_seed: u32,
+    levels: u32,
+) -> GenerationReport {
+    let mut per_level: Vec<(LevelStats, Duration)> = Vec::with_capacity(levels as usize);
+    for i in 0..levels {
+        let mut rng = GameRng::new(first_seed.wrapping_add(i));
+        let mut counters = ThingCounters::default();
+        let knowledge = Knowledge::default();
+        let start = Instant::now();
+        let level = generate_dungeon_level_with(generator, &mut counters, &knowledge, &mut rng);
+        let elapsed = start.elapsed();
+        per_level.push((LevelStats::of(&level), elapsed));
+    }
+
+    let each = |f: fn(&LevelStats) -> f64| {
+        Distribution::from_samples(per_level.iter().map(|(s, _)| f(s)).collect())
+    };
+    GenerationReport {
+        first_seed,
+        levels,
+        depth: generator.depth,
+        room_count: each(|s| s.room_count as f64),
+        gone_rooms: each(|s| s.gone_rooms as f64),
+        room_area: Distribution::from_samples(
+            per_level
+                .iter()
+                .flat_map(|(s, _)| s.room_areas.iter().map(|&a| a as f64))
+                .collect(),
+        ),
+        corridor_length: Distribution::from_samples(
+            per_level
+                .iter()
+                .flat_map(|(s, _)| s.corridor_lengths.iter().map(|&l| l as f64))
+                .collect(),
+        ),
+        dead_ends: each(|s| s.dead_ends as f64),
+        extra_edges: each(|s| s.extra_edges as f64),
+        generation_micros: Distribution::from_samples(
+            per_level
+                .iter()
+                .map(|(_, t)| t.as_secs_f64() * 1_000_000.0)
+                .collect(),
+        ),
+    }
+}
+
+/// A plain-text table, one row per metric.
+impl fmt::Display for GenerationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} levels, seeds {}..{}, depth {}",
+            self.levels,
+            self.first_seed,
+            self.first_seed as u64 + self.levels as u64,
+            self.depth
+        )?;
+        writeln!(
+            f,
+            "{:<22}{:>9}{:>9}{:>9}{:>9}{:>9}{:>9}",
+            "metric", "samples", "min", "mean", "median", "p90", "max"
+        )?;
+        let rows = [
+            ("rooms per level", &self.room_count),
+            ("gone rooms per level", &self.gone_rooms),
+            ("room area", &self.room_area),
+            ("corridor length", &self.corridor_length),
+            ("dead ends per level", &self.dead_ends),
+            ("extra edges per level", &self.extra_edges),
+            ("generation time (us)", &self.generation_micros),
+        ];
+        for (name, d) in rows {
+            writeln!(
+                f,
+                "{:<22}{:>9}{:>9.1}{:>9.1}{:>9.1}{:>9.1}{:>9.1}",
+                name, d.samples, d.min, d.mean, d.median, d.p90, d.max
+            )?;
+        }
+        Ok(())
+    }
+}
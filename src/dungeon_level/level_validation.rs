@@ -0,0 +1,211 @@
+use std::fmt;
+
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::terrain_structure::room::Room;
+
+/// Something wrong with a generated level.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelIssue {
+    /// A room sticks out of the terrain.
+    RoomOutOfBounds { room: usize },
+    /// A room's floor cannot be walked to from the first room.
+    UnreachableRoom { room: usize },
+    /// A door with nothing walkable on its outer side.
+    DeadDoor { room: usize, door: IVec2 },
+    /// A cell of a room's wall that is neither wall nor door, such as a
+    /// passage cut through a corner.
+    BrokenWall { room: usize, pos: IVec2 },
+    /// A passage tile that cannot be walked on.
+    BlockedPassage { passage: usize, tile: IVec2 },
+}
+
+impl fmt::Display for LevelIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelIssue::RoomOutOfBounds { room } => {
+                write!(f, "room {} extends past the edge of the map", room)
+            }
+            LevelIssue::UnreachableRoom { room } => {
+                write!(f, "room {} cannot be reached from the other rooms", room)
+            }
+            LevelIssue::DeadDoor { room, door } => write!(
+                f,
+                "door ({}, {}) of room {} leads nowhere",
+                door.x, door.y, room
+            ),
+            LevelIssue::BrokenWall { room, pos } => write!(
+                f,
+                "wall of room {} is broken at ({}, {})",
+                room, pos.x, pos.y
+            ),
+            LevelIssue::BlockedPassage { passage, tile } => write!(
+                f,
+                "passage {} is blocked at ({}, {})",
+                passage, tile.x, tile.y
+            ),
+        }
+    }
+}
+
+const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+
+/// Check that a level is playable: every room fits on the map, has an
+/// unbroken wall and can be walked to, every door opens onto something, and
+/// every passage tile can be walked on. Returns all problems found; an empty list means the level
+/// passed.
+pub fn validate_level(level: &DungeonLevel) -> Vec<LevelIssue> {
+    let mut issues = Vec::new();
+    let (width, height) = (level.terrain.width as i32, level.terrain.height as i32);
+    let walkable = |pos: IVec2| level.cell_class_at(pos).is_some_and(|c| c.is_walkable());
+
+    for (i, room) in level.rooms.iter().enumerate() {
+        if room.is_gone {
+            continue;
+        }
+        let end = room.pos + room.size;
+        if room.pos.x < 0 || room.pos.y < 0 || end.x > width || end.y > height {
+            issues.push(LevelIssue::RoomOutOfBounds { room: i });
+        }
+        for pos in wall_ring(room) {
+            let intact = level.cell_class_at(pos).is_some_and(|c| {
+                matches!(
+                    c,
+                    TerrainCellClass::WallHorizontal
+                        | TerrainCellClass::WallVertical
+                        | TerrainCellClass::Door
+                )
+            });
+            if !intact {
+                issues.push(LevelIssue::BrokenWall { room: i, pos });
+            }
+        }
+        for &door in &room.doors {
+            let leads_out = DIRECTIONS
+                .iter()
+                .map(|&d| door + d)
+                .any(|p| !on_floor(room, p) && walkable(p));
+            if !leads_out {
+                issues.push(LevelIssue::DeadDoor { room: i, door });
+            }
+        }
+    }
+
+    for (i, passage) in level.passages.iter().enumerate() {
+        for &tile in &passage.tiles {
+            if !walkable(tile) {
+                issues.push(LevelIssue::BlockedPassage { passage: i, tile });
+            }
+        }
+    }
+
+    // Flood fill from the first real room and see which rooms it reaches.
+    let floor_of = |i: usize| {
+        let room = &level.rooms[i];
+        (room.pos.y + 1..room.pos.y + room.size.y - 1)
+            .flat_map(|y| {
+                (room.pos.x + 1..room.pos.x + room.size.x - 1).map(move |x| IVec2::new(x, y))
+            })
+            .find(|&p| walkable(p))
+    };
+    let real_rooms: Vec<usize> = (0..level.rooms.len())
+        .filter(|&i| !level.rooms[i].is_gone)
+        .collect();
+    let Some(start) = real_rooms.first().and_then(|&i| floor_of(i)) else {
+        return issues;
+    };
+
+    let mut reached = vec![false; (width * height).max(0) as usize];
+    let mut stack = vec![start];
+    reached[(start.y * width + start.x) as usize] = true;
+    while let Some(p) = stack.pop() {
+        for d in DIRECTIONS {
+            let n = p + d;
+            if !walkable(n) {
+                continue;
+            }
+            let idx = (n.y * width + n.x) as usize;
+            if !reached[idx] {
+                reached[idx] = true;
+                stack.push(n);
+            }
+        }
+    }
+
+    for &i in &real_rooms {
+        let connected = floor_of(i).is_some_and(|p| reached[(p.y * width + p.x) as usize]);
+        if !connected {
+            issues.push(LevelIssue::UnreachableRoom { room: i });
+        }
+    }
+
+    issues
+}
+
+/// Whether a position is on a room's floor, inside its walls.
+fn on_floor(room: &Room, pos: IVec2) -> bool {
+    pos.x > room.pos.x
+        && pos.y > room.pos.y
+        && pos.x < room.pos.x + room.size.x - 1
+        && pos.y < room.pos.y + room.size.y - 1
+}
+
+/// The positions of a room's walls, corners included.
+fn wall_ring(room: &Room) -> Vec<IVec2> {
+    let (pos, end) = (room.pos, room.pos + room.size - IVec2::ONE);
+    let mut ring = Vec::new();
+    for x in pos.x..=end.x {
+        ring.push(IVec2::new(x, pos.y));
+        ring.push(IVec2::new(x, end.y));
+    }
+    for y in pos.y + 1..end.y {
+        ring.push(IVec2::new(pos.x, y));
+        ring.push(IVec2::new(end.x, y));
+    }
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon_level::dungeon_level::generate_dungeon_level_with;
+    use crate::dungeon_level::terrain_generator::TerrainGenerator;
+    use crate::rng::GameRng;
+    use crate::thing::knowledge::Knowledge;
+    use crate::thing::new_thing::ThingCounters;
+
+    #[test]
+    fn generated_levels_are_sound() {
+        let knowledge = Knowledge::default();
+        for depth in [1, 10] {
+            let generator = TerrainGenerator {
+                depth,
+                ..TerrainGenerator::default()
+            };
+            for seed in 1..=200 {
+                let mut counters = ThingCounters::default();
+                let mut rng = GameRng::new(seed);
+                let level =
+                    generate_dungeon_level_with(&generator, &mut counters, &knowledge, &mut rng);
+                assert_eq!(validate_level(&level), [], "seed {} depth {}", seed, depth);
+            }
+        }
+    }
+
+    #[test]
+    fn a_passage_through_a_corner_breaks_the_wall() {
+        let mut rng = GameRng::new(34);
+        let mut level = generate_dungeon_level_with(
+            &TerrainGenerator::default(),
+            &mut ThingCounters::default(),
+            &Knowledge::default(),
+            &mut rng,
+        );
+        let room = level.rooms.iter().position(|r| !r.is_gone).unwrap();
+        let corner = level.rooms[room].pos;
+        level.terrain.set_passage(corner.x, corner.y);
+        assert!(validate_level(&level).contains(&LevelIssue::BrokenWall { room, pos: corner }));
+    }
+}
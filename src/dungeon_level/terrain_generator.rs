@@ -3,13 +3,21 @@ use rand::Rng;
 
 use crate::terrain::terrain_grid::TerrainGrid;
 use crate::terrain_structure::room::put_room;
-use crate::terrain_structure::room::Room;
+use crate::terrain_structure::room::{Room, RoomKind};
 use crate::terrain_structure::room_grid::RoomAdjacency;
 use crate::terrain_structure::passage::Passage;
 
+/// One chance in `TREAS_ROOM` for a treasure room.
+pub const TREAS_ROOM: u32 = 20;
+
 pub struct TerrainGenerator {
     pub terrain_size: IVec2,
     pub max_rooms: usize,
+    /// Dungeon depth the level is generated for; deeper levels get nastier monsters.
+    pub depth: i32,
+    /// One-in-N chance of a treasure room on a level (Rogue's `TREAS_ROOM`);
+    /// 0 disables treasure rooms.
+    pub treasure_room_chance: u32,
 }
 
 impl Default for TerrainGenerator {
@@ -17,14 +25,15 @@ impl Default for TerrainGenerator {
         TerrainGenerator {
             terrain_size: IVec2::new(80, 24),
             max_rooms: 9,
+            depth: 1,
+            treasure_room_chance: TREAS_ROOM,
         }
     }
 }
 
 impl TerrainGenerator {
-    pub fn generate_rooms(&self) -> Vec<Room> {
+    pub fn generate_rooms<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Room> {
         let mut rooms = Vec::new();
-        let mut rng = rand::rng();
 
         let bsze_x = self.terrain_size.x / 3;
         let bsze_y = self.terrain_size.y / 3;
@@ -34,25 +43,24 @@ impl TerrainGenerator {
         // generate a bool vec contains element of max_rooms
         // left_out of them are true, which means the room is gone
         let mut gone_rooms = vec![false; self.max_rooms];
-        for i in 0..left_out {
+        for _ in 0..left_out {
             let idx = rng.random_range(0..self.max_rooms);
             gone_rooms[idx] = true;
         }
 
-        for i in 0..self.max_rooms {
+        for (i, &is_gone) in gone_rooms.iter().enumerate() {
             // top left corner of each block
             let top_x = (i % 3) as i32 * bsze_x + 1;
             let top_y = (i / 3) as i32 * bsze_y;
 
-            // pos is randomly set based on the top left corner of the block, and the size is randomly set based on the block size
-            let pos_x = top_x + rng.random_range(0..=(bsze_x - 4).max(0));
-            let pos_y = top_y + rng.random_range(0..=(bsze_y - 4).max(0));
-
-            if gone_rooms[i] {
+            if is_gone {
                 // mark gone room
                 // this room is lack from the terrain
-                // the pos of gone room should be random in the block
+                // the pos of gone room should be random in the block, off
+                // its edges so passages through it stay inside the block
                 // this should used in put passages to connect the room with other rooms, and make sure the path is not too long
+                let pos_x = top_x + rng.random_range(0..(bsze_x - 2).max(1)) + 1;
+                let pos_y = top_y + rng.random_range(0..(bsze_y - 2).max(1)) + 1;
                 rooms.push(Room {
                     pos: IVec2::new(pos_x, pos_y),
                     size: IVec2::new(0, 0),
@@ -60,14 +68,23 @@ impl TerrainGenerator {
                     is_dark: false,
                     is_gone: true,
                     doors: Vec::new(),
+                    kind: RoomKind::Normal,
                 });
                 continue;
             }
 
-            // random set position and size of the room
-            // size is randomly choose in the left places
-            let size_x = rng.random_range(0..=(bsze_x - (pos_x - top_x) - 1).max(0)).max(4);
-            let size_y = rng.random_range(0..=(bsze_y - (pos_y - top_y) - 1).max(0)).max(4);
+            // random set position and size of the room, as Rogue does: the
+            // room stays a row and a column short of the next block, so a
+            // passage always has somewhere to turn, and off the top line
+            let (pos_x, pos_y, size_x, size_y) = loop {
+                let size_x = rng.random_range(0..(bsze_x - 4).max(1)) + 4;
+                let size_y = rng.random_range(0..(bsze_y - 4).max(1)) + 4;
+                let pos_x = top_x + rng.random_range(0..(bsze_x - size_x).max(1));
+                let pos_y = top_y + rng.random_range(0..(bsze_y - size_y).max(1));
+                if pos_y != 0 || bsze_y < 6 {
+                    break (pos_x, pos_y, size_x, size_y);
+                }
+            };
 
             rooms.push(Room {
                 pos: IVec2::new(pos_x, pos_y),
@@ -76,6 +93,7 @@ impl TerrainGenerator {
                 is_dark: false,
                 is_gone: false,
                 doors: Vec::new(),
+                kind: RoomKind::Normal,
             });
         }
 
@@ -86,9 +104,8 @@ impl TerrainGenerator {
     ///
     /// This only creates the abstract connection graph; it does not
     /// actually carve corridors on the terrain.
-    pub fn generate_room_connections(&self) -> RoomAdjacency {
-        let mut rng = rand::rng();
-        RoomAdjacency::generate_random_graph(&mut rng)
+    pub fn generate_room_connections<R: Rng + ?Sized>(&self, rng: &mut R) -> RoomAdjacency {
+        RoomAdjacency::generate_random_graph(rng)
     }
 
     /// Put given rooms onto the provided terrain grid.
@@ -118,11 +135,12 @@ impl TerrainGenerator {
     /// already placed rooms on the grid.
     ///
     /// Returns the list of carved passages.
-    pub fn carve_passages(
+    pub fn carve_passages<R: Rng + ?Sized>(
         &self,
         grid: &mut TerrainGrid,
         rooms: &mut [Room],
         adjacency: &RoomAdjacency,
+        rng: &mut R,
     ) -> Vec<Passage> {
         let room_count = rooms.len();
         let mut passages = Vec::new();
@@ -136,7 +154,7 @@ impl TerrainGenerator {
                         (&mut left[i], &mut right[0])
                     };
                     if let Some(p) =
-                        self.carve_corridor_between_rooms(grid, r1, r2, i, j)
+                        self.carve_corridor_between_rooms(grid, r1, r2, i, j, rng)
                     {
                         passages.push(p);
                     }
@@ -150,13 +168,14 @@ impl TerrainGenerator {
     /// Carve a corridor between two rooms using a port of Rogue's `conn`
     /// algorithm: corridors are either horizontal or vertical primary
     /// with a single turn.
-    fn carve_corridor_between_rooms(
+    fn carve_corridor_between_rooms<R: Rng + ?Sized>(
         &self,
         grid: &mut TerrainGrid,
         r1: &mut Room,
         r2: &mut Room,
         idx1: usize,
         idx2: usize,
+        rng: &mut R,
     ) -> Option<Passage> {
         // Map Rogue's 3x3 layout: decide which pair index is "rm",
         // and whether we are going right ('r') or down ('d').
@@ -193,14 +212,12 @@ impl TerrainGenerator {
             return None;
         }
 
-        let mut rng = rand::rng();
-
-        let mut del = IVec2::ZERO;
+        let del;
         let mut spos = from.pos;
         let mut epos = to.pos;
-        let mut turn_delta = IVec2::ZERO;
-        let mut distance: i32 = 0;
-        let mut turn_distance: i32 = 0;
+        let turn_delta;
+        let distance: i32;
+        let turn_distance: i32;
 
         if direc == 'd' {
             // Vertical adjacency: from is above to.
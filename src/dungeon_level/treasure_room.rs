@@ -0,0 +1,84 @@
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::dungeon_level::dungeon_level::{DungeonLevel, MAX_TRIES};
+use crate::terrain_structure::room::RoomKind;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::monster_table::{give_pack, new_monster, rand_monster};
+use crate::thing::new_thing::{new_thing, ThingCounters};
+use crate::thing::object_table::object_cell_class;
+use crate::thing::IS_MEAN;
+
+/// Fewest objects (and monsters) put in a treasure room.
+pub const MIN_TREAS: i32 = 2;
+/// Most objects put in a treasure room.
+pub const MAX_TREAS: i32 = 10;
+
+/// Roll for a treasure room on this level and fill one if the roll comes
+/// up (the `rnd(TREAS_ROOM) == 0` check in Rogue's `put_things`).
+///
+/// `chance` is one-in-N; 0 never makes a treasure room. Returns the index
+/// of the room that became a treasure room.
+pub fn put_treasure_room<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    depth: i32,
+    chance: u32,
+    counters: &mut ThingCounters,
+    knowledge: &Knowledge,
+    rng: &mut R,
+) -> Option<usize> {
+    if chance == 0 || rng.random_range(0..chance) != 0 {
+        return None;
+    }
+    let room_id = level.rnd_room(rng)?;
+    fill_treasure_room(level, room_id, depth, counters, knowledge, rng);
+    Some(room_id)
+}
+
+/// Turn the given room into a treasure room: scatter extra objects on its
+/// floor and fill it with monsters from the next level down that wake up
+/// when the player walks in (Rogue's `treas_room`).
+pub fn fill_treasure_room<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    room_id: usize,
+    depth: i32,
+    counters: &mut ThingCounters,
+    knowledge: &Knowledge,
+    rng: &mut R,
+) {
+    let room = &mut level.rooms[room_id];
+    room.kind = RoomKind::Treasure;
+
+    let floor_area = (room.size.x - 2) * (room.size.y - 2);
+    let spots = (floor_area - MIN_TREAS).min(MAX_TREAS - MIN_TREAS);
+    let num_objects = rnd(rng, spots) + MIN_TREAS;
+
+    for _ in 0..num_objects {
+        let Some(pos) = level.find_floor(Some(room_id), 2 * MAX_TRIES, false, rng) else {
+            continue;
+        };
+        let mut obj = new_thing(rng, counters, knowledge);
+        obj.pos = pos;
+        if let Some(cell) = level.terrain.get_mut(pos.x as usize, pos.y as usize) {
+            cell.cell_class = object_cell_class(obj.kind);
+        }
+        level.objects.push(obj);
+    }
+
+    // Fill up the room with monsters from the next level down.
+    let num_monsters = (rnd(rng, spots) + MIN_TREAS)
+        .max(num_objects + 2)
+        .min(floor_area);
+
+    for _ in 0..num_monsters {
+        let Some(pos) = level.find_floor(Some(room_id), MAX_TRIES, true, rng) else {
+            continue;
+        };
+        let kind = rand_monster(depth + 1, false, rng);
+        let mut tp = new_monster(kind, pos, depth + 1, rng);
+        tp.flags |= IS_MEAN;
+        tp.room_id = Some(room_id);
+        give_pack(&mut tp, rng, counters, knowledge);
+        level.monsters.push(tp);
+    }
+}
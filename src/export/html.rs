@@ -0,0 +1,71 @@
+use std::fmt::Write;
+
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::export::overlay::{write_overlays, Overlays, CELL_HEIGHT, CELL_WIDTH};
+use crate::export::svg::{escape_xml, glyph_at, BACKGROUND};
+
+/// Render a level as a standalone HTML page: the map as colored text, with
+/// the chosen overlays in an SVG laid over it.
+///
+/// The overlay is stretched to the text block, so it lines up whatever
+/// monospace font the browser picks.
+pub fn level_to_html(level: &DungeonLevel, overlays: &Overlays) -> String {
+    let (width, height) = (level.terrain.width as i32, level.terrain.height as i32);
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>xegue level</title>\n<style>\n");
+    let _ = writeln!(out, "body {{ background: {}; margin: 1em; }}", BACKGROUND);
+    let _ = writeln!(
+        out,
+        ".map {{ position: relative; display: inline-block; font: 16px/{}px monospace; }}",
+        CELL_HEIGHT
+    );
+    out.push_str(".map pre { margin: 0; font: inherit; }\n");
+    out.push_str(
+        ".map svg { position: absolute; left: 0; top: 0; width: 100%; height: 100%; pointer-events: none; }\n",
+    );
+    out.push_str("</style>\n</head>\n<body>\n<div class=\"map\">\n<pre>");
+
+    // One span per run of same-colored characters keeps the page small.
+    for y in 0..height {
+        let mut run: Option<&str> = None;
+        for x in 0..width {
+            let (ch, color) = match glyph_at(level, IVec2::new(x, y)) {
+                Some((ch, color)) => (ch, Some(color)),
+                None => (' ', None),
+            };
+            if color != run {
+                if run.is_some() {
+                    out.push_str("</span>");
+                }
+                if let Some(color) = color {
+                    let _ = write!(out, "<span style=\"color: {}\">", color);
+                }
+                run = color;
+            }
+            out.push_str(&escape_xml(ch));
+        }
+        if run.is_some() {
+            out.push_str("</span>");
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n");
+
+    if *overlays != Overlays::default() {
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" preserveAspectRatio=\"none\">",
+            width * CELL_WIDTH,
+            height * CELL_HEIGHT
+        );
+        write_overlays(&mut out, level, overlays);
+        out.push_str("</svg>\n");
+    }
+
+    out.push_str("</div>\n</body>\n</html>\n");
+    out
+}
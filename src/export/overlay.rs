@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+
+/// Width of one map cell in exported drawings, in SVG user units.
+pub const CELL_WIDTH: i32 = 10;
+/// Height of one map cell in exported drawings, in SVG user units.
+pub const CELL_HEIGHT: i32 = 18;
+
+/// Colors the passages cycle through, so neighbouring ones stand apart.
+const PASSAGE_COLORS: [&str; 6] = [
+    "#ff6f61", "#6bd16b", "#5fa8ff", "#f0c040", "#c77dff", "#4dd0e1",
+];
+
+/// Which design aids to draw over an exported level. All off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Overlays {
+    /// Each room's index at its center.
+    pub room_indices: bool,
+    /// Each room's bounding box, walls included.
+    pub room_bounds: bool,
+    /// Each passage as a line through its tiles.
+    pub passages: bool,
+    /// A ring around every door a room knows about, hidden ones included.
+    pub doors: bool,
+    /// A cross where each gone room sits; passages meet there.
+    pub gone_rooms: bool,
+}
+
+impl Overlays {
+    /// Every overlay turned on.
+    pub fn all() -> Self {
+        Overlays {
+            room_indices: true,
+            room_bounds: true,
+            passages: true,
+            doors: true,
+            gone_rooms: true,
+        }
+    }
+}
+
+/// Top-left corner of a cell, in user units.
+fn corner(pos: IVec2) -> IVec2 {
+    IVec2::new(pos.x * CELL_WIDTH, pos.y * CELL_HEIGHT)
+}
+
+/// Center of a cell, in user units.
+fn center(pos: IVec2) -> IVec2 {
+    corner(pos) + IVec2::new(CELL_WIDTH / 2, CELL_HEIGHT / 2)
+}
+
+/// Append the SVG elements of the chosen overlays, in cell-based user units.
+pub(crate) fn write_overlays(out: &mut String, level: &DungeonLevel, overlays: &Overlays) {
+    if overlays.room_bounds {
+        out.push_str(
+            "<g fill=\"none\" stroke=\"#3fa7ff\" stroke-width=\"1.5\" stroke-dasharray=\"4 3\">\n",
+        );
+        for room in level.rooms.iter().filter(|r| !r.is_gone) {
+            let at = corner(room.pos);
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                at.x,
+                at.y,
+                room.size.x * CELL_WIDTH,
+                room.size.y * CELL_HEIGHT
+            );
+        }
+        out.push_str("</g>\n");
+    }
+
+    if overlays.passages {
+        out.push_str(
+            "<g fill=\"none\" stroke-width=\"2\" stroke-linejoin=\"round\" opacity=\"0.8\">\n",
+        );
+        for (i, passage) in level.passages.iter().enumerate() {
+            if passage.tiles.is_empty() {
+                continue;
+            }
+            let points: Vec<String> = passage
+                .tiles
+                .iter()
+                .map(|&t| {
+                    let c = center(t);
+                    format!("{},{}", c.x, c.y)
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "<polyline stroke=\"{}\" points=\"{}\"><title>passage {}: room {} to room {}</title></polyline>",
+                PASSAGE_COLORS[i % PASSAGE_COLORS.len()],
+                points.join(" "),
+                i,
+                passage.room_a,
+                passage.room_b
+            );
+        }
+        out.push_str("</g>\n");
+    }
+
+    if overlays.doors {
+        out.push_str("<g fill=\"none\" stroke=\"#ffcc00\" stroke-width=\"1.5\">\n");
+        for door in level.rooms.iter().flat_map(|r| r.doors.iter()) {
+            let c = center(*door);
+            let _ = writeln!(
+                out,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+                c.x,
+                c.y,
+                CELL_WIDTH * 3 / 4
+            );
+        }
+        out.push_str("</g>\n");
+    }
+
+    if overlays.gone_rooms {
+        out.push_str("<g stroke=\"#ff4fd8\" stroke-width=\"2\">\n");
+        let arm = CELL_WIDTH / 2;
+        for room in level.rooms.iter().filter(|r| r.is_gone) {
+            let c = center(room.pos);
+            let _ = writeln!(
+                out,
+                "<path d=\"M{} {}L{} {}M{} {}L{} {}\"/>",
+                c.x - arm,
+                c.y - arm,
+                c.x + arm,
+                c.y + arm,
+                c.x - arm,
+                c.y + arm,
+                c.x + arm,
+                c.y - arm
+            );
+        }
+        out.push_str("</g>\n");
+    }
+
+    if overlays.room_indices {
+        out.push_str(
+            "<g fill=\"#ffffff\" stroke=\"#000000\" stroke-width=\"3\" paint-order=\"stroke\" \
+             font-family=\"sans-serif\" font-size=\"14\" font-weight=\"bold\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\">\n",
+        );
+        for (i, room) in level.rooms.iter().enumerate() {
+            // Gone rooms are a single point; label them just above it.
+            let c = if room.is_gone {
+                center(room.pos) - IVec2::new(0, CELL_HEIGHT)
+            } else {
+                corner(room.pos)
+                    + IVec2::new(room.size.x * CELL_WIDTH / 2, room.size.y * CELL_HEIGHT / 2)
+            };
+            let _ = writeln!(out, "<text x=\"{}\" y=\"{}\">{}</text>", c.x, c.y, i);
+        }
+        out.push_str("</g>\n");
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::export::overlay::{write_overlays, Overlays, CELL_HEIGHT, CELL_WIDTH};
+use crate::terminal::terminal_symbol::TerminalSymbol;
+use crate::terrain::terrain_cell::TerrainCellClass;
+
+/// Background of exported maps.
+pub(crate) const BACKGROUND: &str = "#101010";
+
+/// Color a monster is drawn in.
+const MONSTER_COLOR: &str = "#ff5050";
+
+/// Color a cell class is drawn in.
+pub(crate) const fn cell_color(class: TerrainCellClass) -> &'static str {
+    match class {
+        TerrainCellClass::Empty => BACKGROUND,
+        TerrainCellClass::Passage => "#8a8a8a",
+        TerrainCellClass::Door => "#c8a040",
+        TerrainCellClass::Floor => "#5a7a5a",
+        TerrainCellClass::Player => "#ffffff",
+        TerrainCellClass::Trap => "#d04040",
+        TerrainCellClass::Stairs => "#40c0c0",
+        TerrainCellClass::Gold => "#ffd700",
+        TerrainCellClass::Potion => "#d070ff",
+        TerrainCellClass::Scroll => "#f0f0a0",
+        TerrainCellClass::Magic => "#ff70a0",
+        TerrainCellClass::Food => "#c08850",
+        TerrainCellClass::Weapon => "#a0c0ff",
+        TerrainCellClass::Armor => "#80a0c0",
+        TerrainCellClass::Amulet => "#ffb040",
+        TerrainCellClass::Ring => "#40e0a0",
+        TerrainCellClass::Stick => "#c08040",
+        TerrainCellClass::WallHorizontal | TerrainCellClass::WallVertical => "#b0b0b0",
+    }
+}
+
+/// What is drawn at a position and in which color: a monster if one stands
+/// there, otherwise the terrain. `None` for empty cells.
+pub(crate) fn glyph_at(level: &DungeonLevel, pos: IVec2) -> Option<(char, &'static str)> {
+    if let Some(monster) = level.monster_at(pos) {
+        return Some((monster.kind, MONSTER_COLOR));
+    }
+    let class = level.cell_class_at(pos)?;
+    if class == TerrainCellClass::Empty {
+        return None;
+    }
+    Some((
+        TerminalSymbol::char_from_terrain_cell_class(class),
+        cell_color(class),
+    ))
+}
+
+/// Escape the characters XML gives a meaning to.
+pub(crate) fn escape_xml(ch: char) -> String {
+    match ch {
+        '&' => "&amp;".into(),
+        '<' => "&lt;".into(),
+        '>' => "&gt;".into(),
+        '"' => "&quot;".into(),
+        _ => ch.to_string(),
+    }
+}
+
+/// Render a level as a standalone SVG document, one character per cell,
+/// with the chosen overlays drawn on top.
+pub fn level_to_svg(level: &DungeonLevel, overlays: &Overlays) -> String {
+    let width = level.terrain.width as i32 * CELL_WIDTH;
+    let height = level.terrain.height as i32 * CELL_HEIGHT;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+        w = width,
+        h = height
+    );
+    let _ = writeln!(
+        out,
+        "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        width, height, BACKGROUND
+    );
+
+    let _ = writeln!(
+        out,
+        "<g font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">",
+        CELL_HEIGHT - 2
+    );
+    for y in 0..level.terrain.height as i32 {
+        for x in 0..level.terrain.width as i32 {
+            let Some((ch, color)) = glyph_at(level, IVec2::new(x, y)) else {
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>",
+                x * CELL_WIDTH + CELL_WIDTH / 2,
+                y * CELL_HEIGHT + CELL_HEIGHT / 2,
+                color,
+                escape_xml(ch)
+            );
+        }
+    }
+    out.push_str("</g>\n");
+
+    write_overlays(&mut out, level, overlays);
+    out.push_str("</svg>\n");
+    out
+}
@@ -0,0 +1,19 @@
+pub mod game_state;
+pub mod player;
+pub mod hunger;
+pub mod daemon;
+pub mod daemons;
+pub mod command;
+pub mod commands;
+pub mod inventory;
+pub mod message;
+pub mod options;
+pub mod potions;
+pub mod scrolls;
+pub mod sticks;
+pub mod rings;
+pub mod armor;
+pub mod search;
+pub mod weapons;
+pub mod save_codec;
+pub mod save;
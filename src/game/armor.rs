@@ -0,0 +1,102 @@
+use crate::game::game_state::GameState;
+use crate::thing::object_table::{LEATHER, R_SUSTARM};
+use crate::thing::{ARMOR, IS_CURSED, IS_KNOW, IS_PROT};
+
+impl GameState {
+    /// Put on the armor under `ch` (Rogue's `wear`). Returns false if no
+    /// time passed.
+    pub fn wear(&mut self, ch: char) -> bool {
+        let Some(kind) = self.player.pack.get(ch).map(|o| o.kind) else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        if self.player.cur_armor.is_some() {
+            let msg = if self.options.terse {
+                "You are already wearing some"
+            } else {
+                "You are already wearing some.  You'll have to take it off first"
+            };
+            self.messages.push(String::from(msg));
+            return false;
+        }
+        if kind != ARMOR {
+            self.messages.push(String::from("You can't wear that"));
+            return true;
+        }
+        if self.is_current(ch) {
+            return false;
+        }
+        self.waste_time();
+        if let Some(obj) = self.player.pack.get_mut(ch) {
+            obj.flags |= IS_KNOW;
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let msg = if self.options.terse {
+                format!("Wearing {}", self.inv_name(obj, true))
+            } else {
+                format!("You are now wearing {}", self.inv_name(obj, true))
+            };
+            self.messages.push(msg);
+        }
+        self.player.cur_armor = Some(ch);
+        true
+    }
+
+    /// Take off the armor being worn, which takes as long as putting it on
+    /// (Rogue's `take_off`). Returns false if no time passed.
+    pub fn take_off(&mut self) -> bool {
+        let Some(ch) = self.player.cur_armor else {
+            let msg = if self.options.terse {
+                "Not wearing armor"
+            } else {
+                "You aren't wearing any armor"
+            };
+            self.messages.push(String::from(msg));
+            return false;
+        };
+        if self.player.pack.get(ch).is_some_and(|o| o.on(IS_CURSED)) {
+            self.messages
+                .push(String::from("You can't.  It appears to be cursed"));
+            return true;
+        }
+        self.waste_time();
+        self.player.cur_armor = None;
+        if let Some(obj) = self.player.pack.get(ch) {
+            let name = self.inv_name(obj, true);
+            let msg = if self.options.terse {
+                format!("Was wearing {}) {}", ch, name)
+            } else {
+                format!("You used to be wearing {}) {}", ch, name)
+            };
+            self.messages.push(msg);
+        }
+        true
+    }
+
+    /// Rust the armor being worn one point, unless it is leather, already
+    /// as bad as it gets, or protected (Rogue's `rust_armor`).
+    pub fn rust_armor(&mut self) {
+        let sustained = self.player.is_wearing(R_SUSTARM);
+        let Some(ch) = self.player.cur_armor else {
+            return;
+        };
+        let Some(arm) = self.player.pack.get_mut(ch) else {
+            return;
+        };
+        if arm.kind != ARMOR || arm.which == LEATHER || arm.armor >= 9 {
+            return;
+        }
+        if arm.on(IS_PROT) || sustained {
+            self.messages
+                .push(String::from("The rust vanishes instantly"));
+            return;
+        }
+        arm.armor += 1;
+        let msg = if self.options.terse {
+            "Your armor weakens"
+        } else {
+            "Your armor appears to be weaker now. Oh my!"
+        };
+        self.messages.push(String::from(msg));
+    }
+}
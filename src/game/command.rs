@@ -0,0 +1,597 @@
+use std::collections::HashMap;
+
+use glam::IVec2;
+
+/* The escape key, which cancels whatever is being typed. */
+pub const ESCAPE: char = '\x1b';
+
+/// The control-key version of a letter (Rogue's `CTRL`).
+pub const fn ctrl(ch: char) -> char {
+    (ch as u8 & 0x1f) as char
+}
+
+/// A key as the interface hands it over.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+    /// The middle of the numeric keypad.
+    Center,
+}
+
+/// What a key is bound to, before any object or direction is asked for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Move(IVec2),
+    Run(IVec2),
+    Search,
+    Rest,
+    PickUp,
+    Eat,
+    Quaff,
+    Read,
+    Wield,
+    Wear,
+    TakeOff,
+    PutOn,
+    Remove,
+    Throw,
+    Zap,
+    Drop,
+    Inventory,
+    Discoveries,
+    Call,
+    PrevMessage,
+    Quit,
+    Save,
+    /// Do the last command again (Rogue's `a`).
+    Again,
+    /// Start a repeat count, for bindings where the digits move.
+    Count,
+}
+
+impl Action {
+    /// Word for the object prompt, if the action works on an object.
+    pub const fn verb(self) -> Option<&'static str> {
+        match self {
+            Action::Eat => Some("eat"),
+            Action::Quaff => Some("quaff"),
+            Action::Read => Some("read"),
+            Action::Wield => Some("wield"),
+            Action::Wear => Some("wear"),
+            Action::PutOn => Some("put on"),
+            Action::Throw => Some("throw"),
+            Action::Zap => Some("zap with"),
+            Action::Drop => Some("drop"),
+            Action::Call => Some("call"),
+            _ => None,
+        }
+    }
+
+    /// Whether the action needs a direction.
+    pub const fn needs_dir(self) -> bool {
+        matches!(self, Action::Throw | Action::Zap)
+    }
+}
+
+/// A command ready to carry out, with everything it needs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Move(IVec2),
+    Run(IVec2),
+    Search,
+    Rest,
+    PickUp,
+    Eat(char),
+    Quaff(char),
+    Read(char),
+    /// Identify an object, after reading a scroll of identify.
+    Identify(char, &'static [i32]),
+    Wield(char),
+    Wear(char),
+    TakeOff,
+    PutOn(char),
+    Remove,
+    Throw(char, IVec2),
+    Zap(char, IVec2),
+    Drop(char),
+    Inventory,
+    Discoveries,
+    /// Call the type of an object something; an empty name forgets it.
+    Call(char, String),
+    /// Show an earlier message again; up to the interface.
+    PrevMessage,
+    /// Leave the game; up to the interface.
+    Quit,
+    /// Save the game and leave; up to the interface.
+    Save,
+}
+
+/* Directions in the order of the vi keys y k u h l b j n. */
+const DIRS: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+const VI_KEYS: [char; 8] = ['y', 'k', 'u', 'h', 'l', 'b', 'j', 'n'];
+const NUMPAD_KEYS: [char; 8] = ['7', '8', '9', '4', '6', '1', '2', '3'];
+const ARROW_KEYS: [Key; 8] = [
+    Key::UpLeft,
+    Key::Up,
+    Key::UpRight,
+    Key::Left,
+    Key::Right,
+    Key::DownLeft,
+    Key::Down,
+    Key::DownRight,
+];
+
+/// Which key goes with which action. Digits not bound to anything start
+/// a repeat count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: HashMap<Key, Action>,
+}
+
+impl KeyMap {
+    /// Rogue's keys: `hjklyubn` to move, capitals to run, digits for
+    /// counts.
+    pub fn vi_keys() -> Self {
+        let mut map = Self::common();
+        for (ch, dir) in VI_KEYS.into_iter().zip(DIRS) {
+            map.bind(Key::Char(ch), Action::Move(dir));
+        }
+        map
+    }
+
+    /// Moves on the number keys; counts start with `n`.
+    pub fn numpad() -> Self {
+        let mut map = Self::common();
+        for (ch, dir) in NUMPAD_KEYS.into_iter().zip(DIRS) {
+            map.bind(Key::Char(ch), Action::Move(dir));
+        }
+        map.bind(Key::Char('5'), Action::Rest);
+        map.bind(Key::Char('n'), Action::Count);
+        map
+    }
+
+    /// Bindings both profiles share.
+    fn common() -> Self {
+        let mut map = Self {
+            bindings: HashMap::new(),
+        };
+        for ((key, ch), dir) in ARROW_KEYS.into_iter().zip(VI_KEYS).zip(DIRS) {
+            map.bind(key, Action::Move(dir));
+            map.bind(Key::Char(ch.to_ascii_uppercase()), Action::Run(dir));
+        }
+        map.bind(Key::Center, Action::Rest);
+        for (ch, action) in [
+            ('s', Action::Search),
+            ('.', Action::Rest),
+            (',', Action::PickUp),
+            ('e', Action::Eat),
+            ('q', Action::Quaff),
+            ('r', Action::Read),
+            ('w', Action::Wield),
+            ('W', Action::Wear),
+            ('T', Action::TakeOff),
+            ('P', Action::PutOn),
+            ('R', Action::Remove),
+            ('t', Action::Throw),
+            ('z', Action::Zap),
+            ('d', Action::Drop),
+            ('i', Action::Inventory),
+            ('D', Action::Discoveries),
+            ('c', Action::Call),
+            (ctrl('P'), Action::PrevMessage),
+            ('Q', Action::Quit),
+            ('S', Action::Save),
+            ('a', Action::Again),
+        ] {
+            map.bind(Key::Char(ch), action);
+        }
+        map
+    }
+
+    /// Bind a key, returning what it was bound to before.
+    pub fn bind(&mut self, key: Key, action: Action) -> Option<Action> {
+        self.bindings.insert(key, action)
+    }
+
+    /// Free a key, returning what it was bound to.
+    pub fn unbind(&mut self, key: Key) -> Option<Action> {
+        self.bindings.remove(&key)
+    }
+
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings.get(&key).copied()
+    }
+
+    /// The direction a key moves in, if it is bound to a move.
+    pub fn direction(&self, key: Key) -> Option<IVec2> {
+        match self.action(key) {
+            Some(Action::Move(dir) | Action::Run(dir)) => Some(dir),
+            _ => None,
+        }
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::vi_keys()
+    }
+}
+
+/// What a key did to the command being typed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// More keys are needed; the prompt says what for, if anything.
+    Pending(Option<String>),
+    /// A command to do `count` times.
+    Ready(Command, i32),
+    /// Escape threw the command away.
+    Cancelled,
+    /// The key means nothing (Rogue's "illegal command").
+    Unknown(Key),
+}
+
+/// What the reader is waiting for.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum Pending {
+    #[default]
+    Command,
+    Item(Action, Option<IVec2>),
+    Dir(Action, Option<char>),
+    Identify(&'static [i32]),
+    /// What to call the object under the letter, typed so far.
+    Name(char, String),
+}
+
+/// Turns keys into commands one at a time, however they were typed
+/// (Rogue's `command` and `get_item`/`get_dir` prompts).
+#[derive(Debug, Clone, Default)]
+pub struct CommandReader {
+    pub keymap: KeyMap,
+    /// Ask for things the short way.
+    pub terse: bool,
+    count: i32,
+    counting: bool,
+    pending: Pending,
+    /// The last command and its count, for `Action::Again`.
+    last: Option<(Command, i32)>,
+}
+
+impl CommandReader {
+    pub fn new(keymap: KeyMap) -> Self {
+        Self {
+            keymap,
+            ..Self::default()
+        }
+    }
+
+    /// Take the next key. Escape throws away what was typed, except that
+    /// a scroll of identify, once read, insists on an answer (Rogue's
+    /// `whatis(TRUE, ...)`).
+    pub fn feed(&mut self, key: Key) -> Input {
+        if let (Key::Char(ESCAPE), Pending::Identify(kinds)) = (key, &self.pending) {
+            return self.ask_identify(kinds);
+        }
+        if key == Key::Char(ESCAPE) {
+            self.reset();
+            return Input::Cancelled;
+        }
+        match std::mem::take(&mut self.pending) {
+            Pending::Command => self.command(key),
+            Pending::Item(action, dir) => match key {
+                Key::Char(ch) => self.step(action, Some(ch), dir),
+                _ => self.step(action, None, dir),
+            },
+            Pending::Dir(action, item) => match self.keymap.direction(key) {
+                Some(dir) => self.step(action, item, Some(dir)),
+                None => self.step(action, item, None),
+            },
+            Pending::Identify(kinds) => match key {
+                Key::Char(ch) => Input::Ready(Command::Identify(ch, kinds), 1),
+                _ => self.ask_identify(kinds),
+            },
+            Pending::Name(ch, mut name) => match key {
+                Key::Char('\n' | '\r') => {
+                    self.reset();
+                    Input::Ready(Command::Call(ch, name), 1)
+                }
+                Key::Char('\x08' | '\x7f') => {
+                    name.pop();
+                    self.ask_name(ch, name)
+                }
+                Key::Char(c) if !c.is_control() => {
+                    name.push(c);
+                    self.ask_name(ch, name)
+                }
+                _ => self.ask_name(ch, name),
+            },
+        }
+    }
+
+    /// Ask what to call an object, showing what is typed so far (Rogue's
+    /// `get_str`).
+    fn ask_name(&mut self, ch: char, name: String) -> Input {
+        let prompt = if self.terse {
+            "Call it: "
+        } else {
+            "What do you want to call it? "
+        };
+        let input = Input::Pending(Some(format!("{}{}", prompt, name)));
+        self.pending = Pending::Name(ch, name);
+        input
+    }
+
+    /// Ask which object a scroll of identify is for; the answer comes
+    /// back as `Command::Identify`.
+    pub fn ask_identify(&mut self, kinds: &'static [i32]) -> Input {
+        self.pending = Pending::Identify(kinds);
+        Input::Pending(Some(self.item_prompt("identify")))
+    }
+
+    /// A key where a command starts: a digit of a count, or a binding.
+    fn command(&mut self, key: Key) -> Input {
+        let action = self.keymap.action(key);
+        if let Key::Char(ch) = key {
+            if let Some(digit) = ch.to_digit(10) {
+                if self.counting || action.is_none() {
+                    self.counting = true;
+                    self.count = self.count.saturating_mul(10).saturating_add(digit as i32);
+                    return Input::Pending(None);
+                }
+            }
+        }
+        match action {
+            None => {
+                self.reset();
+                Input::Unknown(key)
+            }
+            Some(Action::Count) => {
+                self.counting = true;
+                Input::Pending(None)
+            }
+            Some(Action::Again) => {
+                self.reset();
+                match self.last.clone() {
+                    Some((command, count)) => Input::Ready(command, count),
+                    None => Input::Cancelled,
+                }
+            }
+            Some(action) => self.step(action, None, None),
+        }
+    }
+
+    /// Ask for what the action still needs, or hand it over. Throwing
+    /// asks for the direction first, zapping for the stick first.
+    fn step(&mut self, action: Action, item: Option<char>, dir: Option<IVec2>) -> Input {
+        let verb = action.verb();
+        let dir_first = action == Action::Throw;
+        if action.needs_dir() && dir.is_none() && (dir_first || item.is_some()) {
+            self.pending = Pending::Dir(action, item);
+            let prompt = if self.terse {
+                "Direction: "
+            } else {
+                "Which direction? "
+            };
+            return Input::Pending(Some(String::from(prompt)));
+        }
+        if let (Some(verb), None) = (verb, item) {
+            self.pending = Pending::Item(action, dir);
+            return Input::Pending(Some(self.item_prompt(verb)));
+        }
+        let ch = item.unwrap_or_default();
+        if action == Action::Call {
+            return self.ask_name(ch, String::new());
+        }
+        let dir = dir.unwrap_or_default();
+        let command = match action {
+            Action::Move(dir) => Command::Move(dir),
+            Action::Run(dir) => Command::Run(dir),
+            Action::Search => Command::Search,
+            Action::Rest => Command::Rest,
+            Action::PickUp => Command::PickUp,
+            Action::Eat => Command::Eat(ch),
+            Action::Quaff => Command::Quaff(ch),
+            Action::Read => Command::Read(ch),
+            Action::Wield => Command::Wield(ch),
+            Action::Wear => Command::Wear(ch),
+            Action::TakeOff => Command::TakeOff,
+            Action::PutOn => Command::PutOn(ch),
+            Action::Remove => Command::Remove,
+            Action::Throw => Command::Throw(ch, dir),
+            Action::Zap => Command::Zap(ch, dir),
+            Action::Drop => Command::Drop(ch),
+            Action::Inventory => Command::Inventory,
+            Action::Discoveries => Command::Discoveries,
+            Action::Call => return Input::Cancelled,
+            Action::PrevMessage => Command::PrevMessage,
+            Action::Quit => Command::Quit,
+            Action::Save => Command::Save,
+            Action::Again | Action::Count => return Input::Cancelled,
+        };
+        let count = self.count.max(1);
+        self.reset();
+        if !matches!(
+            command,
+            Command::PrevMessage | Command::Quit | Command::Save
+        ) {
+            self.last = Some((command.clone(), count));
+        }
+        Input::Ready(command, count)
+    }
+
+    fn item_prompt(&self, verb: &str) -> String {
+        if self.terse {
+            let mut chars = verb.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            format!("{}{} what? ", first.unwrap_or_default(), chars.as_str())
+        } else {
+            format!("Which object do you want to {}? ", verb)
+        }
+    }
+
+    /// Forget the count and anything half typed.
+    fn reset(&mut self) {
+        self.count = 0;
+        self.counting = false;
+        self.pending = Pending::Command;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(reader: &mut CommandReader, keys: &str) -> Input {
+        let mut input = Input::Pending(None);
+        for ch in keys.chars() {
+            input = reader.feed(Key::Char(ch));
+        }
+        input
+    }
+
+    #[test]
+    fn keys_become_commands() {
+        let mut reader = CommandReader::default();
+        assert_eq!(
+            reader.feed(Key::Char('h')),
+            Input::Ready(Command::Move(IVec2::new(-1, 0)), 1)
+        );
+        assert_eq!(
+            reader.feed(Key::Char('L')),
+            Input::Ready(Command::Run(IVec2::new(1, 0)), 1)
+        );
+        assert_eq!(
+            reader.feed(Key::Up),
+            Input::Ready(Command::Move(IVec2::new(0, -1)), 1)
+        );
+        assert_eq!(reader.feed(Key::Char('X')), Input::Unknown(Key::Char('X')));
+    }
+
+    #[test]
+    fn digits_make_a_count() {
+        let mut reader = CommandReader::default();
+        assert_eq!(
+            feed_all(&mut reader, "12s"),
+            Input::Ready(Command::Search, 12)
+        );
+        assert_eq!(
+            reader.feed(Key::Char('s')),
+            Input::Ready(Command::Search, 1)
+        );
+
+        // On the number pad the digits move, and counts start with `n`.
+        let mut reader = CommandReader::new(KeyMap::numpad());
+        assert_eq!(
+            reader.feed(Key::Char('4')),
+            Input::Ready(Command::Move(IVec2::new(-1, 0)), 1)
+        );
+        // Digits after `n` go to the count; a move ends it with an arrow.
+        assert_eq!(feed_all(&mut reader, "n20"), Input::Pending(None));
+        assert_eq!(
+            reader.feed(Key::Left),
+            Input::Ready(Command::Move(IVec2::new(-1, 0)), 20)
+        );
+    }
+
+    #[test]
+    fn prompts_ask_for_the_object_and_direction() {
+        let mut reader = CommandReader::default();
+        assert_eq!(
+            reader.feed(Key::Char('q')),
+            Input::Pending(Some(String::from("Which object do you want to quaff? ")))
+        );
+        assert_eq!(
+            reader.feed(Key::Char('c')),
+            Input::Ready(Command::Quaff('c'), 1)
+        );
+
+        assert_eq!(
+            reader.feed(Key::Char('t')),
+            Input::Pending(Some(String::from("Which direction? ")))
+        );
+        assert!(matches!(
+            reader.feed(Key::Char('l')),
+            Input::Pending(Some(_))
+        ));
+        assert_eq!(
+            reader.feed(Key::Char('d')),
+            Input::Ready(Command::Throw('d', IVec2::new(1, 0)), 1)
+        );
+
+        reader.terse = true;
+        assert_eq!(
+            reader.feed(Key::Char('z')),
+            Input::Pending(Some(String::from("Zap with what? ")))
+        );
+    }
+
+    #[test]
+    fn again_repeats_the_last_command() {
+        let mut reader = CommandReader::default();
+        assert_eq!(reader.feed(Key::Char('a')), Input::Cancelled);
+        feed_all(&mut reader, "3r");
+        assert_eq!(
+            reader.feed(Key::Char('b')),
+            Input::Ready(Command::Read('b'), 3)
+        );
+        // Looking back at messages is not something to repeat.
+        reader.feed(Key::Char(ctrl('P')));
+        assert_eq!(
+            reader.feed(Key::Char('a')),
+            Input::Ready(Command::Read('b'), 3)
+        );
+    }
+
+    #[test]
+    fn escape_throws_away_what_was_typed() {
+        let mut reader = CommandReader::default();
+        assert_eq!(feed_all(&mut reader, "5w\x1b"), Input::Cancelled);
+        assert_eq!(
+            reader.feed(Key::Char('s')),
+            Input::Ready(Command::Search, 1)
+        );
+
+        // Except that identify insists on an answer.
+        let kinds: &'static [i32] = &[];
+        reader.ask_identify(kinds);
+        assert!(matches!(
+            reader.feed(Key::Char(ESCAPE)),
+            Input::Pending(Some(_))
+        ));
+        assert_eq!(
+            reader.feed(Key::Char('e')),
+            Input::Ready(Command::Identify('e', kinds), 1)
+        );
+    }
+
+    #[test]
+    fn call_reads_a_name_up_to_enter() {
+        let mut reader = CommandReader::default();
+        feed_all(&mut reader, "cd");
+        assert_eq!(
+            feed_all(&mut reader, "fiz\x7fx"),
+            Input::Pending(Some(String::from("What do you want to call it? fix")))
+        );
+        assert_eq!(
+            reader.feed(Key::Char('\r')),
+            Input::Ready(Command::Call('d', String::from("fix")), 1)
+        );
+        assert_eq!(feed_all(&mut reader, "cdab\x1b"), Input::Cancelled);
+    }
+}
@@ -0,0 +1,287 @@
+use glam::IVec2;
+
+use crate::dice::rnd;
+use crate::game::command::Command;
+use crate::game::daemon::Phase;
+use crate::game::game_state::GameState;
+use crate::game::inventory::PickUp;
+use crate::game::scrolls::ReadOutcome;
+use crate::terrain::pathfinding::is_walkable;
+use crate::terrain::terrain_cell::{TerrainCellClass, F_SEEN};
+use crate::thing::chase::{see_monst, wake_monster};
+use crate::thing::object_table::R_STEALTH;
+use crate::thing::{IS_BLIND, IS_HASTE, IS_HELD, IS_HUH};
+
+/// What carrying out a command came to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// No time went by.
+    Free,
+    /// The hero used up a turn.
+    Turn,
+    /// A scroll of identify was read, or the object named for it would
+    /// not do; ask which object it is for (see
+    /// `CommandReader::ask_identify`).
+    Identify(&'static [i32]),
+    /// Lines to show in a window; no time went by.
+    Show(Vec<String>),
+}
+
+impl Outcome {
+    fn of(after: bool) -> Self {
+        if after {
+            Outcome::Turn
+        } else {
+            Outcome::Free
+        }
+    }
+
+    /// Whether the hero used up a turn.
+    pub fn took_time(&self) -> bool {
+        matches!(self, Outcome::Turn | Outcome::Identify(_))
+    }
+}
+
+impl GameState {
+    /// Carry out a command `count` times, or until it takes no time,
+    /// something is said or the hero dies. Running goes on until the hero
+    /// stops moving or comes to a door. The outcome is a turn if any of
+    /// the repeats took time.
+    pub fn perform(&mut self, command: &Command, count: i32) -> Outcome {
+        let (command, count, running) = match *command {
+            Command::Run(dir) => (Command::Move(dir), i32::MAX, true),
+            ref command => (command.clone(), count.max(1), false),
+        };
+        let mut outcome = Outcome::Free;
+        let mut took_time = false;
+        for _ in 0..count {
+            let said = self.messages.len();
+            let pos = self.player.pos();
+            outcome = self.command(&command);
+            took_time |= outcome.took_time();
+            if outcome != Outcome::Turn || self.killer.is_some() || self.messages.len() > said {
+                break;
+            }
+            let here = self.dungeon.level.cell_class_at(self.player.pos());
+            if running && (pos == self.player.pos() || here == Some(TerrainCellClass::Door)) {
+                break;
+            }
+        }
+        if took_time && outcome == Outcome::Free {
+            return Outcome::Turn;
+        }
+        outcome
+    }
+
+    /// Carry out one command as part of a turn (Rogue's `command`). A
+    /// turn starts with the daemons and fuses that go before the hero,
+    /// gives a hasted hero two actions, and ends with the rings and the
+    /// daemons and fuses that go after. Turns the hero cannot act in go
+    /// by after it. What the hero sensed last is forgotten.
+    pub fn command(&mut self, command: &Command) -> Outcome {
+        if self.killer.is_some() {
+            return Outcome::Free;
+        }
+        self.detection = None;
+        if self.actions_left == 0 {
+            self.do_daemons(Phase::Before);
+            self.do_fuses(Phase::Before);
+            self.actions_left = if self.player.creature.on(IS_HASTE) {
+                2
+            } else {
+                1
+            };
+        }
+        let outcome = self.execute(command);
+        // Asking again about a scroll of identify costs nothing more.
+        let retry = matches!(command, Command::Identify(..));
+        if outcome.took_time() && !retry {
+            self.actions_left -= 1;
+            if self.player.no_command > 0 {
+                self.actions_left = 0;
+            }
+            if self.actions_left == 0 {
+                self.ring_turn();
+                self.do_daemons(Phase::After);
+                self.do_fuses(Phase::After);
+            }
+            while self.player.no_command > 0 && self.killer.is_none() {
+                self.player.no_command -= 1;
+                if self.player.no_command == 0 {
+                    self.messages.push(String::from("You can move again"));
+                }
+                self.end_turn();
+            }
+        }
+        self.see_around();
+        outcome
+    }
+
+    /// Do what a command says, without the rest of the turn.
+    pub fn execute(&mut self, command: &Command) -> Outcome {
+        match *command {
+            Command::Move(dir) | Command::Run(dir) => Outcome::of(self.do_move(dir)),
+            Command::Search => {
+                self.search();
+                Outcome::Turn
+            }
+            Command::Rest => Outcome::Turn,
+            Command::PickUp => match self.pick_up() {
+                PickUp::Nothing => {
+                    let msg = if self.options.terse {
+                        "Nothing here"
+                    } else {
+                        "There is nothing here to pick up"
+                    };
+                    self.messages.push(String::from(msg));
+                    Outcome::Free
+                }
+                PickUp::Floating => {
+                    self.messages
+                        .push(String::from("You can't.  You're floating off the ground!"));
+                    Outcome::Free
+                }
+                _ => Outcome::Turn,
+            },
+            Command::Eat(ch) => Outcome::of(self.eat(ch)),
+            Command::Quaff(ch) => Outcome::of(self.quaff(ch)),
+            Command::Read(ch) => match self.read_scroll(ch) {
+                ReadOutcome::NotRead => Outcome::Free,
+                ReadOutcome::Read => Outcome::Turn,
+                ReadOutcome::Identify(kinds) => Outcome::Identify(kinds),
+            },
+            Command::Identify(ch, kinds) => {
+                if self.whatis(ch, kinds) {
+                    Outcome::Free
+                } else {
+                    Outcome::Identify(kinds)
+                }
+            }
+            Command::Wield(ch) => Outcome::of(self.wield(ch)),
+            Command::Wear(ch) => Outcome::of(self.wear(ch)),
+            Command::TakeOff => Outcome::of(self.take_off()),
+            Command::PutOn(ch) => Outcome::of(self.ring_on(ch, None)),
+            Command::Remove => Outcome::of(self.ring_off(None)),
+            Command::Throw(ch, dir) => Outcome::of(self.throw(ch, dir)),
+            Command::Zap(ch, dir) => Outcome::of(self.zap(ch, dir)),
+            Command::Drop(ch) => Outcome::of(self.drop(ch)),
+            Command::Inventory => Outcome::Show(self.inventory(None)),
+            Command::Discoveries => Outcome::Show(self.discovered(None)),
+            Command::Call(ch, ref name) => {
+                self.call(ch, name);
+                Outcome::Free
+            }
+            Command::PrevMessage | Command::Quit | Command::Save => Outcome::Free,
+        }
+    }
+
+    /// Step the hero one square, fighting whatever is in the way and
+    /// picking up whatever is there (Rogue's `do_move`). A confused hero
+    /// mostly stumbles off some other way. Returns false if no time
+    /// passed.
+    pub fn do_move(&mut self, dir: IVec2) -> bool {
+        let hero = &self.player.creature;
+        let mut dir = dir;
+        if hero.on(IS_HUH) && rnd(&mut self.rng, 5) != 0 {
+            dir = IVec2::new(rnd(&mut self.rng, 3) - 1, rnd(&mut self.rng, 3) - 1);
+        }
+        let level = &self.dungeon.level;
+        let pos = hero.pos + dir;
+        if dir == IVec2::ZERO || !self.diag_ok(hero.pos, pos) {
+            return false;
+        }
+        if hero.on(IS_HELD) && level.monster_at(pos).is_none_or(|m| m.kind != 'F') {
+            self.messages.push(String::from("You are being held"));
+            return true;
+        }
+        if level.monster_at(pos).is_some() {
+            self.melee(pos);
+            return true;
+        }
+        if !self.hero_can_enter(pos) {
+            return false;
+        }
+        let hero = &mut self.player.creature;
+        hero.pos = pos;
+        hero.room_id = level.room_at(pos);
+        if level.objects.iter().any(|o| o.pos == pos) {
+            self.pick_up();
+        }
+        self.look();
+        true
+    }
+
+    /// Let every monster next to the hero or in view notice the hero, as
+    /// Rogue's `look(TRUE)` does.
+    fn look(&mut self) {
+        let hero = &self.player.creature;
+        let level = &self.dungeon.level;
+        let near: Vec<IVec2> = level
+            .monsters
+            .iter()
+            .filter(|m| (m.pos - hero.pos).abs().max_element() <= 1 || see_monst(level, hero, m))
+            .map(|m| m.pos)
+            .collect();
+        let stealthy = self.player.is_wearing(R_STEALTH);
+        for pos in near {
+            wake_monster(
+                &mut self.dungeon.level,
+                pos,
+                &self.player.creature,
+                stealthy,
+                &mut self.rng,
+            );
+        }
+    }
+
+    /// Mark what the hero can see as seen: the whole of a lit room the
+    /// hero is in and the squares around the hero (Rogue's `enter_room`
+    /// and `look`). A blind hero sees nothing new.
+    pub fn see_around(&mut self) {
+        if self.player.creature.on(IS_BLIND) {
+            return;
+        }
+        let pos = self.player.pos();
+        let level = &mut self.dungeon.level;
+        let (start, end) = match level.room_at(pos) {
+            Some(r) if !level.is_dark_at(pos) => {
+                let room = &level.rooms[r];
+                (room.pos, room.pos + room.size)
+            }
+            _ => (pos - IVec2::ONE, pos + IVec2::splat(2)),
+        };
+        let end = end.min(IVec2::new(
+            level.terrain.width as i32,
+            level.terrain.height as i32,
+        ));
+        for y in start.y.max(0)..end.y {
+            for x in start.x.max(0)..end.x {
+                if let Some(cell) = level.terrain.get_mut(x as usize, y as usize) {
+                    cell.flags |= F_SEEN;
+                }
+            }
+        }
+    }
+
+    /// Whether a diagonal step is open on both corners (Rogue's `diag_ok`).
+    fn diag_ok(&self, from: IVec2, to: IVec2) -> bool {
+        if from.x == to.x || from.y == to.y {
+            return true;
+        }
+        self.hero_can_enter(IVec2::new(from.x, to.y))
+            && self.hero_can_enter(IVec2::new(to.x, from.y))
+    }
+
+    /// Whether the hero can step onto a cell (Rogue's `step_ok`). Secret
+    /// doors and passages are solid until found.
+    fn hero_can_enter(&self, pos: IVec2) -> bool {
+        is_walkable(&self.dungeon.level.terrain, pos)
+    }
+
+    /// A whole turn the hero cannot act in: rings at work, then the
+    /// daemons and fuses.
+    fn end_turn(&mut self) {
+        self.ring_turn();
+        self.waste_time();
+    }
+}
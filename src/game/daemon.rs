@@ -0,0 +1,141 @@
+/* Time of a delayed action that recurs every turn rather than burning down. */
+pub const DAEMON: i32 = -1;
+
+/// When in a turn a delayed action runs: before or after the hero moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    Before,
+    After,
+}
+
+/// What a daemon or fuse does when it goes off (Rogue's `d_func`).
+///
+/// Naming the effect instead of storing a function keeps the schedule
+/// plain data, so it saves and restores with the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Effect {
+    /// Natural healing (`doctor`).
+    Doctor,
+    /// Digesting food (`stomach`).
+    Stomach,
+    /// Monsters chasing the hero (`runners`).
+    Runners,
+    /// Start rolling for wandering monsters (`swander`).
+    Swander,
+    /// Maybe bring in a wandering monster (`rollwand`).
+    Rollwand,
+    /// Confusion wears off (`unconfuse`).
+    Unconfuse,
+    /// Seeing invisible wears off (`unsee`).
+    Unsee,
+    /// Blindness wears off (`sight`).
+    Sight,
+    /// Haste wears off (`nohaste`).
+    Nohaste,
+    /// Hallucination wears off (`come_down`).
+    ComeDown,
+    /// Levitation wears off (`land`).
+    Land,
+    /// Sensing monsters wears off (`turn_see`).
+    TurnSee,
+}
+
+/// One scheduled daemon or fuse (Rogue's `struct delayed_action`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelayedAction {
+    pub phase: Phase,
+    pub effect: Effect,
+    pub arg: i32,
+    /// Turns until a fuse goes off, or `DAEMON`.
+    pub time: i32,
+}
+
+/// The daemons and fuses of a game (Rogue's `d_list`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scheduler {
+    pub actions: Vec<DelayedAction>,
+    /// Turns since the last roll for a wandering monster (Rogue's
+    /// `between` in `rollwand`).
+    pub between: i32,
+}
+
+impl Scheduler {
+    fn find(&self, effect: Effect) -> Option<usize> {
+        self.actions.iter().position(|a| a.effect == effect)
+    }
+
+    /// Start a daemon that runs every turn (Rogue's `start_daemon`).
+    pub fn start_daemon(&mut self, effect: Effect, arg: i32, phase: Phase) {
+        self.actions.push(DelayedAction {
+            phase,
+            effect,
+            arg,
+            time: DAEMON,
+        });
+    }
+
+    /// Remove a daemon from the list (Rogue's `kill_daemon`).
+    pub fn kill_daemon(&mut self, effect: Effect) {
+        if let Some(i) = self.find(effect) {
+            self.actions.remove(i);
+        }
+    }
+
+    /// Start a fuse that goes off once after `time` turns (Rogue's `fuse`).
+    pub fn fuse(&mut self, effect: Effect, arg: i32, time: i32, phase: Phase) {
+        self.actions.push(DelayedAction {
+            phase,
+            effect,
+            arg,
+            time,
+        });
+    }
+
+    /// Increase the time until a fuse goes off (Rogue's `lengthen`).
+    pub fn lengthen(&mut self, effect: Effect, xtime: i32) {
+        if let Some(i) = self.find(effect) {
+            self.actions[i].time += xtime;
+        }
+    }
+
+    /// Put out a fuse before it goes off (Rogue's `extinguish`).
+    pub fn extinguish(&mut self, effect: Effect) {
+        self.kill_daemon(effect);
+    }
+
+    /// Whether a daemon or fuse for `effect` is scheduled.
+    pub fn is_scheduled(&self, effect: Effect) -> bool {
+        self.find(effect).is_some()
+    }
+
+    /// The daemons of a phase, to run this turn (Rogue's `do_daemons`).
+    pub fn daemons_due(&self, phase: Phase) -> Vec<(Effect, i32)> {
+        self.actions
+            .iter()
+            .filter(|a| a.phase == phase && a.time == DAEMON)
+            .map(|a| (a.effect, a.arg))
+            .collect()
+    }
+
+    /// Burn down the fuses of a phase by a turn and take out those that
+    /// go off, in order (Rogue's `do_fuses`).
+    pub fn burn_fuses(&mut self, phase: Phase) -> Vec<(Effect, i32)> {
+        let mut due = Vec::new();
+        self.actions.retain_mut(|a| {
+            if a.phase != phase || a.time <= 0 {
+                return true;
+            }
+            a.time -= 1;
+            if a.time == 0 {
+                due.push((a.effect, a.arg));
+                return false;
+            }
+            true
+        });
+        due
+    }
+}
@@ -0,0 +1,173 @@
+use crate::dice::{roll, spread};
+use crate::game::daemon::{Effect, Phase};
+use crate::game::game_state::GameState;
+use crate::game::rings::{LEFT, RIGHT};
+use crate::thing::chase::runners;
+use crate::thing::fight::{attack, AttackOutcome};
+use crate::thing::monster_table::wanderer;
+use crate::thing::{CAN_SEE, IS_BLIND, IS_CANC, IS_HALU, IS_HASTE, IS_HUH, IS_LEVIT, SEE_MONST};
+
+/* Turns until wandering monsters start showing up. */
+pub const WANDERTIME: i32 = 70;
+
+impl GameState {
+    /// Start the daemons and fuses every game begins with.
+    pub fn start_daemons(&mut self) {
+        self.daemons.start_daemon(Effect::Runners, 0, Phase::After);
+        self.daemons.start_daemon(Effect::Doctor, 0, Phase::After);
+        let time = spread(&mut self.rng, WANDERTIME);
+        self.daemons.fuse(Effect::Swander, 0, time, Phase::After);
+        self.daemons.start_daemon(Effect::Stomach, 0, Phase::After);
+    }
+
+    /// Run the daemons of a phase (Rogue's `do_daemons`).
+    pub fn do_daemons(&mut self, phase: Phase) {
+        for (effect, arg) in self.daemons.daemons_due(phase) {
+            self.run_effect(effect, arg);
+        }
+    }
+
+    /// Burn down the fuses of a phase and set off those that run out
+    /// (Rogue's `do_fuses`).
+    pub fn do_fuses(&mut self, phase: Phase) {
+        for (effect, arg) in self.daemons.burn_fuses(phase) {
+            self.run_effect(effect, arg);
+        }
+    }
+
+    /// Let a turn go by while the hero is busy, as when taking armor off
+    /// (Rogue's `waste_time`).
+    pub fn waste_time(&mut self) {
+        self.do_daemons(Phase::Before);
+        self.do_fuses(Phase::Before);
+        self.do_daemons(Phase::After);
+        self.do_fuses(Phase::After);
+    }
+
+    /// Do what a daemon or fuse does. Nothing more happens once the hero
+    /// is dead.
+    pub fn run_effect(&mut self, effect: Effect, _arg: i32) {
+        if self.killer.is_some() {
+            return;
+        }
+        let hero = &mut self.player.creature;
+        match effect {
+            Effect::Doctor => self.player.doctor(&mut self.rng),
+            Effect::Stomach => {
+                let extra = self.player.ring_eat(LEFT, &mut self.rng)
+                    + self.player.ring_eat(RIGHT, &mut self.rng);
+                if self
+                    .player
+                    .stomach(extra, &mut self.messages, &mut self.rng)
+                {
+                    self.killer = Some('s');
+                }
+            }
+            Effect::Runners => self.runners(),
+            Effect::Swander => {
+                self.daemons
+                    .start_daemon(Effect::Rollwand, 0, Phase::Before);
+            }
+            Effect::Rollwand => self.rollwand(),
+            Effect::Unconfuse => {
+                hero.flags &= !IS_HUH;
+                let msg = if hero.on(IS_HALU) {
+                    "You feel less trippy now"
+                } else {
+                    "You feel less confused now"
+                };
+                self.messages.push(String::from(msg));
+            }
+            Effect::Unsee => hero.flags &= !CAN_SEE,
+            Effect::Sight => {
+                if hero.on(IS_BLIND) {
+                    self.daemons.extinguish(Effect::Sight);
+                    hero.flags &= !IS_BLIND;
+                    let msg = if hero.on(IS_HALU) {
+                        "Far out!  Everything is all cosmic again"
+                    } else {
+                        "The veil of darkness lifts"
+                    };
+                    self.messages.push(String::from(msg));
+                }
+            }
+            Effect::Nohaste => {
+                hero.flags &= !IS_HASTE;
+                self.messages
+                    .push(String::from("You feel yourself slowing down"));
+            }
+            Effect::ComeDown => {
+                if hero.on(IS_HALU) {
+                    hero.flags &= !IS_HALU;
+                    if !hero.on(IS_BLIND) {
+                        self.messages
+                            .push(String::from("Everything looks SO boring now."));
+                    }
+                }
+            }
+            Effect::Land => {
+                hero.flags &= !IS_LEVIT;
+                self.messages
+                    .push(String::from("You float gently to the ground"));
+            }
+            Effect::TurnSee => hero.flags &= !SEE_MONST,
+        }
+    }
+
+    /// Move the running monsters and let those next to the hero attack.
+    fn runners(&mut self) {
+        let hero = &self.player.creature;
+        let attackers = runners(
+            &mut self.dungeon.level,
+            hero,
+            &mut self.pathfinder,
+            &mut self.rng,
+        );
+        for idx in attackers {
+            self.player.quiet = 0;
+            let hero_arm = self.player.armor_class();
+            let outcome = attack(
+                &mut self.dungeon,
+                &mut self.player.creature,
+                hero_arm,
+                idx,
+                &mut self.messages,
+                &mut self.rng,
+            );
+            match outcome {
+                AttackOutcome::Killed { killer } => {
+                    self.killer = Some(killer);
+                    return;
+                }
+                AttackOutcome::Hit => {
+                    // If an aquator hits, the hero can lose armor class.
+                    let tp = &self.dungeon.level.monsters[idx];
+                    if tp.kind == 'A' && !tp.on(IS_CANC) {
+                        self.rust_armor();
+                    }
+                }
+                AttackOutcome::Missed => {}
+            }
+        }
+    }
+
+    /// Every few turns, maybe bring in a wandering monster; once one comes,
+    /// wait a while before rolling again (Rogue's `rollwand`).
+    fn rollwand(&mut self) {
+        self.daemons.between += 1;
+        if self.daemons.between >= 4 {
+            if roll(&mut self.rng, 1, 6) == 4 {
+                wanderer(
+                    &mut self.dungeon.level,
+                    self.dungeon.depth,
+                    &self.player.creature,
+                    &mut self.rng,
+                );
+                self.daemons.kill_daemon(Effect::Rollwand);
+                let time = spread(&mut self.rng, WANDERTIME);
+                self.daemons.fuse(Effect::Swander, 0, time, Phase::Before);
+            }
+            self.daemons.between = 0;
+        }
+    }
+}
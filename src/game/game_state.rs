@@ -0,0 +1,102 @@
+use glam::IVec2;
+
+use crate::dice::rnd;
+use crate::dungeon_level::dungeon::Dungeon;
+use crate::game::daemon::Scheduler;
+use crate::game::options::Options;
+use crate::game::player::Player;
+use crate::game::potions::Detection;
+use crate::rng::GameRng;
+use crate::terrain::pathfinding::Pathfinder;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::new_thing::{init_weapon, ThingCounters};
+use crate::thing::object_table::{ARROW, A_CLASS, BOW, MACE, RING_MAIL};
+use crate::thing::{ObjectThing, ARMOR, FOOD, IS_KNOW};
+
+/// Everything that makes up a game in progress.
+pub struct GameState {
+    pub rng: GameRng,
+    pub dungeon: Dungeon,
+    pub player: Player,
+    pub counters: ThingCounters,
+    /// Messages shown so far, oldest first.
+    pub messages: Vec<String>,
+    pub daemons: Scheduler,
+    pub knowledge: Knowledge,
+    pub options: Options,
+    /// What the hero has just sensed, until the next command; not saved.
+    pub detection: Option<Detection>,
+    /// Actions the hero has left this turn, two when hasted (Rogue's
+    /// `ntimes`); not saved.
+    pub actions_left: i32,
+    /// What killed the hero, once dead (see `killname`).
+    pub killer: Option<char>,
+    /// Route finder the monsters share; not saved.
+    pub pathfinder: Pathfinder,
+}
+
+impl GameState {
+    /// Start a new game from a seed: generate the first level, put the
+    /// player on a random floor spot in it, shuffle how unknown things look,
+    /// hand out the starting kit and start the daemons.
+    pub fn new(seed: u32) -> Self {
+        let mut rng = GameRng::new(seed);
+        let mut counters = ThingCounters::default();
+        let knowledge = Knowledge::new(&mut rng);
+        let dungeon = Dungeon::new(&mut counters, &knowledge, &mut rng);
+        let pos = dungeon
+            .level
+            .find_floor(None, 0, true, &mut rng)
+            .unwrap_or(IVec2::ZERO);
+
+        let mut player = Player::new(pos);
+        player.creature.room_id = dungeon.level.room_at(pos);
+
+        let mut game = Self {
+            rng,
+            dungeon,
+            player,
+            counters,
+            messages: Vec::new(),
+            daemons: Scheduler::default(),
+            knowledge,
+            options: Options::default(),
+            detection: None,
+            actions_left: 0,
+            killer: None,
+            pathfinder: Pathfinder::default(),
+        };
+        game.init_player();
+        game.start_daemons();
+        game.see_around();
+        game
+    }
+
+    /// Give the hero a food ration and Rogue's starting kit: ring mail on,
+    /// a +1,+1 mace in hand, a +1 bow and some arrows (Rogue's
+    /// `init_player`).
+    fn init_player(&mut self) {
+        self.player.pack.add(ObjectThing::new(FOOD, 0));
+
+        let mut armor = ObjectThing::new(ARMOR, RING_MAIL);
+        armor.armor = A_CLASS[RING_MAIL as usize] - 1;
+        armor.flags |= IS_KNOW;
+        self.player.cur_armor = Some(self.player.pack.add(armor));
+
+        let mut mace = init_weapon(MACE, &mut self.rng, &mut self.counters);
+        mace.hplus = 1;
+        mace.dplus = 1;
+        mace.flags |= IS_KNOW;
+        self.player.cur_weapon = Some(self.player.pack.add(mace));
+
+        let mut bow = init_weapon(BOW, &mut self.rng, &mut self.counters);
+        bow.hplus = 1;
+        bow.flags |= IS_KNOW;
+        self.player.pack.add(bow);
+
+        let mut arrows = init_weapon(ARROW, &mut self.rng, &mut self.counters);
+        arrows.count = rnd(&mut self.rng, 15) + 25;
+        arrows.flags |= IS_KNOW;
+        self.player.pack.add(arrows);
+    }
+}
@@ -0,0 +1,219 @@
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::game::game_state::GameState;
+use crate::game::player::Player;
+use crate::thing::{ObjectThing, FOOD, IS_RUN};
+
+/* Food clock, in turns. */
+pub const HUNGERTIME: i32 = 1300; /* food in a fresh stomach or a meal */
+pub const MORETIME: i32 = 150; /* weak below this, hungry below twice it */
+pub const STOMACHSIZE: i32 = 2000; /* most food the hero can hold */
+pub const STARVETIME: i32 = 850; /* turns of fainting before starving */
+
+/// Name of the fruit when the player has not chosen one (Rogue's `fruit`).
+pub const DEFAULT_FRUIT: &str = "slime-mold";
+
+/// How hungry the hero is (Rogue's `hungry_state`), as shown on the
+/// status line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HungerState {
+    #[default]
+    NotHungry,
+    Hungry,
+    Weak,
+    Faint,
+}
+
+impl HungerState {
+    /// Word shown on the status line; empty when not hungry.
+    pub fn status_name(self) -> &'static str {
+        match self {
+            HungerState::NotHungry => "",
+            HungerState::Hungry => "Hungry",
+            HungerState::Weak => "Weak",
+            HungerState::Faint => "Faint",
+        }
+    }
+}
+
+impl Player {
+    /// Digest food for one turn (Rogue's `stomach` daemon). `extra` is any
+    /// food eaten beyond the usual one unit, from rings, less one while
+    /// carrying the Amulet. Returns true if the hero starved to death.
+    pub fn stomach<R: Rng + ?Sized>(
+        &mut self,
+        extra: i32,
+        messages: &mut Vec<String>,
+        rng: &mut R,
+    ) -> bool {
+        if self.food_left <= 0 {
+            let food = self.food_left;
+            self.food_left -= 1;
+            if food < -STARVETIME {
+                return true;
+            }
+            // The hero is fainting.
+            if self.no_command > 0 || rnd(rng, 5) != 0 {
+                return false;
+            }
+            self.no_command += rnd(rng, 8) + 4;
+            self.hungry_state = HungerState::Faint;
+            self.creature.flags &= !IS_RUN;
+            messages.push(String::from("You faint from lack of food"));
+        } else {
+            let oldfood = self.food_left;
+            self.food_left -= extra + 1;
+            if self.food_left < MORETIME && oldfood >= MORETIME {
+                self.hungry_state = HungerState::Weak;
+                messages.push(String::from("You are starting to feel weak"));
+            } else if self.food_left < 2 * MORETIME && oldfood >= 2 * MORETIME {
+                self.hungry_state = HungerState::Hungry;
+                messages.push(String::from("You are starting to get hungry"));
+            }
+        }
+        false
+    }
+
+    /// Eat a piece of food (Rogue's `eat`). Returns false if `obj` is not
+    /// food; otherwise the caller takes one from the pack. A ration can
+    /// taste awful, which is worth a point of experience.
+    pub fn eat<R: Rng + ?Sized>(
+        &mut self,
+        obj: &ObjectThing,
+        fruit: &str,
+        messages: &mut Vec<String>,
+        rng: &mut R,
+    ) -> bool {
+        if obj.kind != FOOD {
+            messages.push(String::from("Ugh, you would get ill if you ate that"));
+            return false;
+        }
+        self.food_left = self.food_left.max(0);
+        self.food_left = (self.food_left + HUNGERTIME - 200 + rnd(rng, 400)).min(STOMACHSIZE);
+        self.hungry_state = HungerState::NotHungry;
+
+        if obj.which == 1 {
+            messages.push(format!("My, that was a yummy {}", fruit));
+        } else if rnd(rng, 100) > 70 {
+            messages.push(String::from("Yuk, this food tastes awful"));
+            if let Some(level) = self.gain_exp(1, rng) {
+                messages.push(format!("Welcome to level {}", level));
+            }
+        } else {
+            messages.push(String::from("Yum, that tasted good"));
+        }
+        true
+    }
+}
+
+impl GameState {
+    /// Eat the food under letter `ch` (Rogue's `eat`). Returns false if
+    /// nothing was eaten.
+    pub fn eat(&mut self, ch: char) -> bool {
+        let Some(obj) = self.player.pack.get(ch).cloned() else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        let fruit = &self.options.fruit;
+        if !self
+            .player
+            .eat(&obj, fruit, &mut self.messages, &mut self.rng)
+        {
+            return false;
+        }
+        if obj.count <= 1 && self.player.cur_weapon == Some(ch) {
+            self.player.cur_weapon = None;
+        }
+        self.player.pack.leave(ch, false);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::IVec2;
+
+    use super::*;
+    use crate::rng::GameRng;
+    use crate::thing::POTION;
+
+    fn digest(player: &mut Player, turns: i32, messages: &mut Vec<String>) -> bool {
+        let mut rng = GameRng::new(1);
+        (0..turns).any(|_| player.stomach(0, messages, &mut rng))
+    }
+
+    #[test]
+    fn gets_hungry_then_weak() {
+        let mut player = Player::new(IVec2::ZERO);
+        let mut messages = Vec::new();
+        player.food_left = 2 * MORETIME;
+        assert!(!digest(&mut player, 1, &mut messages));
+        assert_eq!(player.hungry_state, HungerState::Hungry);
+        assert_eq!(messages, ["You are starting to get hungry"]);
+
+        assert!(!digest(&mut player, MORETIME, &mut messages));
+        assert_eq!(player.hungry_state, HungerState::Weak);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1], "You are starting to feel weak");
+    }
+
+    #[test]
+    fn rings_make_food_go_faster() {
+        let mut player = Player::new(IVec2::ZERO);
+        let mut rng = GameRng::new(1);
+        player.food_left = 1000;
+        player.stomach(2, &mut Vec::new(), &mut rng);
+        assert_eq!(player.food_left, 997);
+    }
+
+    #[test]
+    fn an_empty_stomach_faints_then_starves() {
+        let mut player = Player::new(IVec2::ZERO);
+        let mut messages = Vec::new();
+        player.food_left = 0;
+        assert!(!digest(&mut player, 50, &mut messages));
+        assert_eq!(player.hungry_state, HungerState::Faint);
+        assert!(player.no_command > 0);
+        assert!(messages.contains(&String::from("You faint from lack of food")));
+
+        player.food_left = -STARVETIME;
+        assert!(!digest(&mut player, 1, &mut messages));
+        assert!(digest(&mut player, 1, &mut messages));
+    }
+
+    #[test]
+    fn eating_fills_the_stomach() {
+        let mut player = Player::new(IVec2::ZERO);
+        let mut rng = GameRng::new(2);
+        let mut messages = Vec::new();
+        player.food_left = 10;
+        player.hungry_state = HungerState::Weak;
+        assert!(player.eat(
+            &ObjectThing::new(FOOD, 0),
+            DEFAULT_FRUIT,
+            &mut messages,
+            &mut rng
+        ));
+        assert_eq!(player.hungry_state, HungerState::NotHungry);
+        assert!((10 + HUNGERTIME - 200..10 + HUNGERTIME + 200).contains(&player.food_left));
+
+        player.food_left = STOMACHSIZE;
+        player.eat(
+            &ObjectThing::new(FOOD, 1),
+            DEFAULT_FRUIT,
+            &mut messages,
+            &mut rng,
+        );
+        assert_eq!(player.food_left, STOMACHSIZE);
+        assert_eq!(messages.last().unwrap(), "My, that was a yummy slime-mold");
+
+        assert!(!player.eat(
+            &ObjectThing::new(POTION, 0),
+            DEFAULT_FRUIT,
+            &mut messages,
+            &mut rng
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::game::game_state::GameState;
+use crate::game::rings::LEFT;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::naming::{inv_name, NameStyle};
+use crate::thing::object_table::{object_cell_class, S_SCARE};
+use crate::thing::pack::is_mult;
+use crate::thing::{ObjectThing, GOLD, IS_CURSED, IS_FOUND, IS_LEVIT, POTION, RING, SCROLL, STICK};
+
+/// What picking up at the hero's feet did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickUp {
+    /// Nothing is lying there.
+    Nothing,
+    /// Gold went into the purse.
+    Gold(i32),
+    /// The object went into the pack under this letter.
+    Added(char),
+    /// The pack is full and the object stays on the floor.
+    NoRoom,
+    /// A scare monster scroll crumbled.
+    Dust,
+    /// The hero is levitating and cannot reach the floor.
+    Floating,
+}
+
+impl GameState {
+    /// Name an object the way the player's options ask for, lowercase for
+    /// the middle of a sentence.
+    pub fn inv_name(&self, obj: &ObjectThing, lowercase: bool) -> String {
+        let style = NameStyle {
+            terse: self.options.terse,
+            lowercase,
+        };
+        let mut name = inv_name(obj, &self.knowledge, &self.options.fruit, style);
+        if self.player.armor().is_some_and(|a| std::ptr::eq(a, obj)) {
+            name += " (being worn)";
+        }
+        if self.player.weapon().is_some_and(|w| std::ptr::eq(w, obj)) {
+            name += " (weapon in hand)";
+        }
+        match self.player.hand_of(obj) {
+            Some(LEFT) => name += " (on left hand)",
+            Some(_) => name += " (on right hand)",
+            None => {}
+        }
+        name
+    }
+
+    /// Whether the object under `ch` is in hand, worn or on a finger,
+    /// saying so if it is (Rogue's `is_current`).
+    pub fn is_current(&mut self, ch: char) -> bool {
+        let player = &self.player;
+        let current = player.cur_weapon == Some(ch)
+            || player.cur_armor == Some(ch)
+            || player.cur_ring.contains(&Some(ch));
+        if current {
+            let msg = if self.options.terse {
+                "In use"
+            } else {
+                "That's already in use"
+            };
+            self.messages.push(String::from(msg));
+        }
+        current
+    }
+
+    /// Whether the object under `ch` can be let go of, taking it out of
+    /// hand, off or off a finger first if need be; cursed things stay
+    /// where they are (Rogue's `dropcheck`).
+    pub fn dropcheck(&mut self, ch: char) -> bool {
+        let player = &self.player;
+        let hand = player.cur_ring.iter().position(|&r| r == Some(ch));
+        if player.cur_weapon != Some(ch) && player.cur_armor != Some(ch) && hand.is_none() {
+            return true;
+        }
+        let Some(obj) = player.pack.get(ch) else {
+            return true;
+        };
+        if obj.on(IS_CURSED) {
+            self.messages
+                .push(String::from("You can't.  It appears to be cursed"));
+            return false;
+        }
+        let (which, arm) = (obj.which, obj.armor);
+        if self.player.cur_weapon == Some(ch) {
+            self.player.cur_weapon = None;
+        } else if self.player.cur_armor == Some(ch) {
+            self.waste_time();
+            self.player.cur_armor = None;
+        } else if let Some(hand) = hand {
+            self.player.cur_ring[hand] = None;
+            self.ring_removed(which, arm);
+        }
+        true
+    }
+
+    /// Floor under the hero once whatever lay there is gone.
+    fn bare_floor(&self) -> TerrainCellClass {
+        let level = &self.dungeon.level;
+        match level.room_at(self.player.pos()) {
+            Some(r) if !level.rooms[r].is_gone && !level.rooms[r].is_maze => {
+                TerrainCellClass::Floor
+            }
+            _ => TerrainCellClass::Passage,
+        }
+    }
+
+    /// Reset the hero's cell to bare floor.
+    fn clear_floor(&mut self) {
+        let pos = self.player.pos();
+        let class = self.bare_floor();
+        if let Some(cell) = self
+            .dungeon
+            .level
+            .terrain
+            .get_mut(pos.x as usize, pos.y as usize)
+        {
+            cell.cell_class = class;
+        }
+    }
+
+    /// Add gold to the purse (Rogue's `money`).
+    pub fn money(&mut self, value: i32) {
+        self.player.purse += value;
+        self.clear_floor();
+        if value > 0 {
+            self.messages
+                .push(format!("You found {} gold pieces", value));
+        }
+    }
+
+    /// Pick up whatever the hero is standing on (Rogue's `pick_up`),
+    /// unless the hero is floating above it.
+    pub fn pick_up(&mut self) -> PickUp {
+        if self.player.creature.on(IS_LEVIT) {
+            return PickUp::Floating;
+        }
+        let pos = self.player.pos();
+        let level = &mut self.dungeon.level;
+        let Some(i) = level.objects.iter().position(|o| o.pos == pos) else {
+            return PickUp::Nothing;
+        };
+
+        let obj = &level.objects[i];
+        if obj.kind == GOLD {
+            let value = obj.armor;
+            level.objects.remove(i);
+            self.money(value);
+            return PickUp::Gold(value);
+        }
+        if obj.kind == SCROLL && obj.which == S_SCARE && obj.on(IS_FOUND) {
+            level.objects.remove(i);
+            self.clear_floor();
+            self.messages
+                .push(String::from("The scroll turns to dust as you pick it up"));
+            return PickUp::Dust;
+        }
+        if !self.player.pack.room_for(obj) {
+            let name = self.inv_name(&self.dungeon.level.objects[i], true);
+            let msg = if self.options.terse {
+                format!("No room.  Moved onto {}", name)
+            } else {
+                format!("There's no room in your pack.  You moved onto {}", name)
+            };
+            self.messages.push(msg);
+            return PickUp::NoRoom;
+        }
+
+        let obj = self.dungeon.level.objects.remove(i);
+        self.clear_floor();
+        let ch = self.player.pack.add(obj);
+        if let Some(obj) = self.player.pack.get_mut(ch) {
+            // A scare monster scroll crumbles the next time it is picked up.
+            if obj.kind == SCROLL && obj.which == S_SCARE {
+                obj.flags |= IS_FOUND;
+            }
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let msg = if self.options.terse {
+                format!("{} ({})", self.inv_name(obj, false), ch)
+            } else {
+                format!("You now have {} ({})", self.inv_name(obj, true), ch)
+            };
+            self.messages.push(msg);
+        }
+        PickUp::Added(ch)
+    }
+
+    /// Drop the object under letter `ch` where the hero stands (Rogue's
+    /// `drop`). Potions, scrolls and food go one at a time; anything else
+    /// goes as a whole stack. Returns false if nothing was dropped.
+    pub fn drop(&mut self, ch: char) -> bool {
+        let pos = self.player.pos();
+        let here = self.dungeon.level.cell_class_at(pos);
+        if !matches!(
+            here,
+            Some(TerrainCellClass::Floor | TerrainCellClass::Passage)
+        ) || self.dungeon.level.objects.iter().any(|o| o.pos == pos)
+        {
+            self.messages
+                .push(String::from("There is something there already"));
+            return false;
+        }
+        let Some(kind) = self.player.pack.get(ch).map(|o| o.kind) else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        if !self.dropcheck(ch) {
+            return false;
+        }
+        let Some(mut obj) = self.player.pack.leave(ch, !is_mult(kind)) else {
+            return false;
+        };
+
+        obj.pos = pos;
+        if let Some(cell) = self
+            .dungeon
+            .level
+            .terrain
+            .get_mut(pos.x as usize, pos.y as usize)
+        {
+            cell.cell_class = object_cell_class(obj.kind);
+        }
+        self.messages
+            .push(format!("Dropped {}", self.inv_name(&obj, true)));
+        self.dungeon.level.objects.push(obj);
+        true
+    }
+
+    /// Lines listing the pack, or just one type in it (Rogue's
+    /// `inventory`). Empty after saying so if there is nothing to list.
+    pub fn inventory(&mut self, kind: Option<i32>) -> Vec<String> {
+        let lines: Vec<String> = self
+            .player
+            .pack
+            .inventory(kind)
+            .map(|o| format!("{}) {}", o.pack_ch, self.inv_name(o, false)))
+            .collect();
+        if lines.is_empty() {
+            let msg = if kind.is_none() {
+                "You are empty handed"
+            } else {
+                "You don't have anything appropriate"
+            };
+            self.messages.push(String::from(msg));
+        }
+        lines
+    }
+
+    /// Call the type of the object under letter `ch` something, or forget
+    /// what it was called if `name` is empty (Rogue's `call`).
+    pub fn call(&mut self, ch: char, name: &str) {
+        let Some(obj) = self.player.pack.get(ch) else {
+            self.messages.push(String::from("You don't have that"));
+            return;
+        };
+        let (kind, which) = (obj.kind, obj.which);
+        if !matches!(kind, POTION | SCROLL | RING | STICK) {
+            self.messages
+                .push(String::from("You can't call that anything"));
+        } else if !self.knowledge.call(kind, which, Some(name.to_string())) {
+            self.messages
+                .push(String::from("That has already been identified"));
+        }
+    }
+
+    /// Lines listing the types of object found out about so far (Rogue's
+    /// `discovered`). Empty after saying so if there are none.
+    pub fn discovered(&mut self, kind: Option<i32>) -> Vec<String> {
+        let lines = self.knowledge.discoveries(kind);
+        if lines.is_empty() {
+            self.messages
+                .push(String::from("Haven't discovered anything"));
+        }
+        lines
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::VecDeque;
+
+/* Prompt shown when the top line is full and another message waits. */
+pub const MORE: &str = "--More--";
+/* Messages kept for looking back over with Ctrl-P. */
+pub const HISTORY_SIZE: usize = 100;
+
+/// The top line of the screen, where messages show up (Rogue's `msg`,
+/// `addmsg` and `endmsg`), apart from how it gets drawn. Short messages
+/// share the line; when the next one does not fit, the line waits on
+/// `--More--` until the player acknowledges it.
+#[derive(Debug, Clone)]
+pub struct MessageLine {
+    /// Columns the line has room for.
+    pub width: usize,
+    /// Say things the short way (Rogue's `terse` option).
+    pub terse: bool,
+    /// What the line shows now, less the prompt.
+    line: String,
+    /// Whether the line waits on a key before going on.
+    more: bool,
+    /// Messages not shown yet, each no wider than the line.
+    queue: VecDeque<String>,
+    /// Messages shown so far, oldest first.
+    history: VecDeque<String>,
+    /// How far back Ctrl-P has gone, while it is in use.
+    recall: Option<usize>,
+}
+
+impl MessageLine {
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            terse: false,
+            line: String::new(),
+            more: false,
+            queue: VecDeque::new(),
+            history: VecDeque::new(),
+            recall: None,
+        }
+    }
+
+    /// Put up a message, first letter capitalized (Rogue's `msg`). One
+    /// too long for the line is broken up between words.
+    pub fn msg(&mut self, text: &str) {
+        let text = capitalize(text);
+        if text.is_empty() {
+            return;
+        }
+        self.recall = None;
+        if self.history.len() >= HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(text.clone());
+        self.queue.extend(wrap(&text, self.room()));
+        self.flush();
+    }
+
+    /// Put up the long or the short form of a message, as terse mode has it.
+    pub fn say(&mut self, verbose: &str, terse: &str) {
+        let text = if self.terse { terse } else { verbose };
+        self.msg(text);
+    }
+
+    /// Put up everything the game has said since last time, in order.
+    pub fn post(&mut self, messages: &mut Vec<String>) {
+        for text in messages.drain(..) {
+            self.msg(&text);
+        }
+    }
+
+    /// Whether the line waits on `--More--` for a key.
+    pub fn is_waiting(&self) -> bool {
+        self.more
+    }
+
+    /// The player answered `--More--`: go on to the next message.
+    pub fn acknowledge(&mut self) {
+        if self.more {
+            self.line.clear();
+            self.more = false;
+            self.flush();
+        }
+    }
+
+    /// The player hit escape at `--More--`: drop the rest of the messages.
+    pub fn skip(&mut self) {
+        self.queue.clear();
+        self.line.clear();
+        self.more = false;
+    }
+
+    /// Wipe the line before the next command (Rogue's `msg("")`).
+    pub fn clear(&mut self) {
+        if !self.more {
+            self.line.clear();
+            self.recall = None;
+        }
+    }
+
+    /// Show the message before the one last recalled (Rogue's `^P`);
+    /// pressed again and again, it goes further back.
+    pub fn previous(&mut self) {
+        if self.more {
+            return;
+        }
+        let back = self.recall.map_or(0, |n| n + 1);
+        let Some(text) = self
+            .history
+            .len()
+            .checked_sub(back + 1)
+            .and_then(|i| self.history.get(i))
+        else {
+            return;
+        };
+        self.line = wrap(text, self.width).swap_remove(0);
+        self.recall = Some(back);
+    }
+
+    /// Messages shown so far, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    /// What to draw on the top line, prompt and all.
+    pub fn display(&self) -> String {
+        if self.more {
+            format!("{} {}", self.line, MORE)
+        } else {
+            self.line.clone()
+        }
+    }
+
+    /// Columns a message can take and still leave room for the prompt.
+    fn room(&self) -> usize {
+        self.width.saturating_sub(MORE.len() + 1).max(1)
+    }
+
+    /// Move waiting messages onto the line while they fit.
+    fn flush(&mut self) {
+        while !self.more {
+            let Some(next) = self.queue.front() else {
+                break;
+            };
+            if self.line.is_empty() {
+                self.line = next.clone();
+            } else if self.line.len() + 2 + next.len() <= self.room() {
+                self.line.push_str("  ");
+                self.line.push_str(next);
+            } else {
+                self.more = true;
+                break;
+            }
+            self.queue.pop_front();
+        }
+    }
+}
+
+/// Capitalize a message, except one that starts with a pack letter.
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if !text[first.len_utf8()..].starts_with(')') => {
+            first.to_uppercase().chain(chars).collect()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Break a message into pieces no wider than `room`, between words where
+/// it can.
+fn wrap(text: &str, room: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while rest.len() > room {
+        let mut cut = room;
+        while !rest.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if cut == 0 {
+            cut = rest.chars().next().map_or(1, char::len_utf8);
+        }
+        let at = rest[..cut].rfind(' ').filter(|&i| i > 0).unwrap_or(cut);
+        pieces.push(rest[..at].trim_end().to_string());
+        rest = rest[at..].trim_start();
+    }
+    if !rest.is_empty() || pieces.is_empty() {
+        pieces.push(rest.to_string());
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_messages_share_the_line() {
+        let mut line = MessageLine::new(80);
+        line.msg("you hit the bat");
+        line.msg("the bat misses");
+        assert!(!line.is_waiting());
+        assert_eq!(line.display(), "You hit the bat  The bat misses");
+    }
+
+    #[test]
+    fn a_full_line_waits_on_more() {
+        let mut line = MessageLine::new(30);
+        line.msg("You hit the bat");
+        line.msg("The bat misses");
+        assert!(line.is_waiting());
+        assert_eq!(line.display(), "You hit the bat --More--");
+
+        line.acknowledge();
+        assert!(!line.is_waiting());
+        assert_eq!(line.display(), "The bat misses");
+
+        line.msg("One");
+        line.msg("Two three four five");
+        line.msg("Six");
+        assert!(line.is_waiting());
+        line.skip();
+        assert_eq!(line.display(), "");
+    }
+
+    #[test]
+    fn long_messages_wrap_between_words() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            ["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), [""]);
+
+        let mut line = MessageLine::new(20);
+        line.msg("you feel a wrenching sensation in your gut");
+        assert_eq!(line.display(), "You feel a --More--");
+        line.acknowledge();
+        assert_eq!(line.display(), "wrenching --More--");
+    }
+
+    #[test]
+    fn previous_goes_back_through_the_history() {
+        let mut line = MessageLine::new(80);
+        line.msg("first");
+        line.clear();
+        line.msg("second");
+        line.clear();
+        line.previous();
+        assert_eq!(line.display(), "Second");
+        line.previous();
+        assert_eq!(line.display(), "First");
+        line.previous();
+        assert_eq!(line.display(), "First");
+        assert_eq!(line.history().collect::<Vec<_>>(), ["First", "Second"]);
+    }
+
+    #[test]
+    fn pack_letters_stay_lowercase() {
+        assert_eq!(capitalize("a) some food"), "a) some food");
+        assert_eq!(capitalize("welcome"), "Welcome");
+    }
+}
@@ -0,0 +1,20 @@
+use crate::game::hunger::DEFAULT_FRUIT;
+
+/// Choices the player makes about the game (Rogue's options).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Options {
+    /// Shorter messages (Rogue's `terse`).
+    pub terse: bool,
+    /// What to call the fruit (Rogue's `fruit`).
+    pub fruit: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            terse: false,
+            fruit: DEFAULT_FRUIT.to_string(),
+        }
+    }
+}
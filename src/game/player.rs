@@ -0,0 +1,240 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::{rnd, roll};
+use crate::game::hunger::{HungerState, HUNGERTIME};
+use crate::game::rings::{LEFT, RIGHT};
+use crate::thing::object_table::{R_ADDSTR, R_PROTECT, R_REGEN};
+use crate::thing::pack::Pack;
+use crate::thing::stats::Stats;
+use crate::thing::{MonsterThing, ObjectThing};
+
+/// Experience needed to leave each level; 0 ends the table (Rogue's `e_levels`).
+pub const E_LEVELS: [i32; 21] = [
+    10, 20, 40, 80, 160, 320, 640, 1300, 2600, 5200, 13000, 26000, 50000, 100000, 200000, 400000,
+    800000, 2000000, 4000000, 8000000, 0,
+];
+
+/* Quiet turns, less two per level, before a low-level hero heals a point. */
+pub const HEALTIME: i32 = 20;
+
+/* Bounds on the hero's strength. */
+pub const MIN_STRENGTH: i32 = 3;
+pub const MAX_STRENGTH: i32 = 31;
+
+/// Strength changed by `amt`, kept within bounds (Rogue's `add_str`).
+pub fn add_str(strength: i32, amt: i32) -> i32 {
+    (strength + amt).clamp(MIN_STRENGTH, MAX_STRENGTH)
+}
+
+/// The hero: the creature on the map plus what only the hero has.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+    /// The hero as a creature, like Rogue's `player`; its stats are
+    /// Rogue's `pstats`.
+    pub creature: MonsterThing,
+    /// Strength the hero can be restored to (Rogue's `max_stats.s_str`).
+    pub max_strength: i32,
+    pub pack: Pack,
+    /// Gold carried (Rogue's `purse`).
+    pub purse: i32,
+    /// Turns since the hero last healed (Rogue's `quiet`).
+    pub quiet: i32,
+    /// Turns of food left; below zero the hero is fainting (Rogue's `food_left`).
+    pub food_left: i32,
+    pub hungry_state: HungerState,
+    /// Turns the hero can do nothing, as when fainting (Rogue's `no_command`).
+    pub no_command: i32,
+    /// Pack letter of the weapon in hand (Rogue's `cur_weapon`).
+    pub cur_weapon: Option<char>,
+    /// Pack letter of the armor being worn (Rogue's `cur_armor`).
+    pub cur_armor: Option<char>,
+    /// Pack letters of the rings on the left and right hands (Rogue's
+    /// `cur_ring`).
+    pub cur_ring: [Option<char>; 2],
+}
+
+impl Player {
+    /// Create the hero with Rogue's starting stats (`INIT_STATS`).
+    pub fn new(pos: IVec2) -> Self {
+        let creature = MonsterThing {
+            pos,
+            turn: true,
+            kind: '@',
+            disguise: '@',
+            old_ch: ' ',
+            dest: None,
+            flags: 0,
+            stats: Stats::new(16, 0, 1, 10, 12, "1x4"),
+            room_id: None,
+            pack: Vec::new(),
+            reserved: 0,
+        };
+        Self {
+            max_strength: creature.stats.strength,
+            creature,
+            pack: Pack::default(),
+            purse: 0,
+            quiet: 0,
+            food_left: HUNGERTIME,
+            hungry_state: HungerState::NotHungry,
+            no_command: 0,
+            cur_weapon: None,
+            cur_armor: None,
+            cur_ring: [None; 2],
+        }
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.creature.stats
+    }
+
+    pub fn pos(&self) -> IVec2 {
+        self.creature.pos
+    }
+
+    /// The weapon in hand, if any.
+    pub fn weapon(&self) -> Option<&ObjectThing> {
+        self.cur_weapon.and_then(|ch| self.pack.get(ch))
+    }
+
+    /// The armor being worn, if any.
+    pub fn armor(&self) -> Option<&ObjectThing> {
+        self.cur_armor.and_then(|ch| self.pack.get(ch))
+    }
+
+    /// Armor class the hero is hit against: that of the armor worn, or
+    /// the hero's own, less what rings of protection give.
+    pub fn armor_class(&self) -> i32 {
+        let arm = self.armor().map_or(self.creature.stats.armor, |a| a.armor);
+        arm - self.ring_sum(R_PROTECT)
+    }
+
+    /// Level reached with a given amount of experience.
+    pub fn level_for(exp: i32) -> i32 {
+        let i = E_LEVELS
+            .iter()
+            .position(|&e| e == 0 || e > exp)
+            .unwrap_or(E_LEVELS.len() - 1);
+        i as i32 + 1
+    }
+
+    /// Check whether the hero has gone up a level and give the hit points
+    /// for it (Rogue's `check_level`). Returns the new level if it rose.
+    pub fn check_level<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<i32> {
+        let stats = &mut self.creature.stats;
+        let olevel = stats.level;
+        let level = Self::level_for(stats.exp);
+        stats.level = level;
+        if level > olevel {
+            let add = roll(rng, level - olevel, 10);
+            stats.max_hp += add;
+            stats.hp += add;
+            return Some(level);
+        }
+        None
+    }
+
+    /// Add experience, as for a kill, and check for a new level.
+    pub fn gain_exp<R: Rng + ?Sized>(&mut self, exp: i32, rng: &mut R) -> Option<i32> {
+        self.creature.stats.exp += exp;
+        self.check_level(rng)
+    }
+
+    /// Go up to the next level (Rogue's `raise_level`).
+    pub fn raise_level<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<i32> {
+        let level = self.creature.stats.level;
+        self.creature.stats.exp =
+            E_LEVELS[(level - 1).clamp(0, E_LEVELS.len() as i32 - 1) as usize] + 1;
+        self.check_level(rng)
+    }
+
+    /// Heal naturally over one turn (Rogue's `doctor` daemon). Low levels
+    /// heal a point after a quiet stretch; from level 8 on, every third
+    /// quiet turn heals several. Each ring of regeneration heals a point
+    /// more.
+    pub fn doctor<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let regen = [LEFT, RIGHT]
+            .into_iter()
+            .filter(|&hand| self.is_ring(hand, R_REGEN))
+            .count() as i32;
+        let stats = &mut self.creature.stats;
+        let lv = stats.level;
+        let ohp = stats.hp;
+        self.quiet += 1;
+        if lv < 8 {
+            if self.quiet + (lv << 1) > HEALTIME {
+                stats.hp += 1;
+            }
+        } else if self.quiet >= 3 {
+            stats.hp += rnd(rng, lv - 7) + 1;
+        }
+        stats.hp += regen;
+        if ohp != stats.hp {
+            stats.hp = stats.hp.min(stats.max_hp);
+            self.quiet = 0;
+        }
+    }
+
+    /// Change the hero's strength, raising the maximum if it is passed
+    /// without the help of rings (Rogue's `chg_str`).
+    pub fn chg_str(&mut self, amt: i32) {
+        if amt == 0 {
+            return;
+        }
+        let stats = &mut self.creature.stats;
+        stats.strength = add_str(stats.strength, amt);
+        let comp = add_str(stats.strength, -self.ring_sum(R_ADDSTR));
+        self.max_strength = self.max_strength.max(comp);
+    }
+
+    /// Whether the hero has died.
+    pub fn is_dead(&self) -> bool {
+        self.creature.stats.hp <= 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+
+    #[test]
+    fn levels_follow_the_experience_table() {
+        assert_eq!(Player::level_for(0), 1);
+        assert_eq!(Player::level_for(9), 1);
+        assert_eq!(Player::level_for(10), 2);
+        assert_eq!(Player::level_for(39), 3);
+        assert_eq!(Player::level_for(8_000_000), 21);
+        assert_eq!(Player::level_for(i32::MAX), 21);
+    }
+
+    #[test]
+    fn check_level_adds_hit_points_for_each_new_level() {
+        let mut rng = GameRng::new(3);
+        let mut player = Player::new(IVec2::ZERO);
+        assert_eq!(player.check_level(&mut rng), None);
+
+        let (hp, max_hp) = (player.stats().hp, player.stats().max_hp);
+        player.creature.stats.exp = 45;
+        assert_eq!(player.check_level(&mut rng), Some(4));
+        let stats = player.stats();
+        assert_eq!(stats.level, 4);
+        let add = stats.max_hp - max_hp;
+        assert!((3..=30).contains(&add), "{} hit points for 3 levels", add);
+        assert_eq!(stats.hp - hp, add);
+
+        assert_eq!(player.check_level(&mut rng), None);
+        assert_eq!(player.stats().max_hp, max_hp + add);
+    }
+
+    #[test]
+    fn raise_level_goes_up_exactly_one() {
+        let mut rng = GameRng::new(5);
+        let mut player = Player::new(IVec2::ZERO);
+        assert_eq!(player.raise_level(&mut rng), Some(2));
+        assert_eq!(player.stats().exp, 11);
+        assert_eq!(player.raise_level(&mut rng), Some(3));
+    }
+}
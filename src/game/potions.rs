@@ -0,0 +1,307 @@
+use glam::IVec2;
+
+use crate::dice::{rnd, roll, spread};
+use crate::game::daemon::{Effect, Phase};
+use crate::game::game_state::GameState;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::object_table::{
+    A_CLASS, P_BLIND, P_CONFUSE, P_HASTE, P_HEALING, P_LEVIT, P_LSD, P_MFIND, P_POISON, P_RAISE,
+    P_RESTORE, P_SEEINVIS, P_STRENGTH, P_TFIND, P_XHEAL, R_SUSTSTR,
+};
+use crate::thing::{
+    ObjectThing, Thing, AMULET, ARMOR, CAN_SEE, IS_BLIND, IS_HALU, IS_HASTE, IS_HUH, IS_LEVIT,
+    IS_PROT, IS_RUN, POTION, RING, SCROLL, SEE_MONST, STICK, WEAPON,
+};
+
+/* Durations of timed effects, in turns. */
+pub const HUHDURATION: i32 = 20; /* confusion, and sensing monsters */
+pub const SEEDURATION: i32 = 850; /* hallucination, blindness, seeing invisible */
+pub const LEVITDURATION: i32 = 30; /* levitation */
+
+/// Spots the hero has sensed, drawn as `class` over the map until the next
+/// command (Rogue's `show_win`). Not saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    pub class: TerrainCellClass,
+    pub positions: Vec<IVec2>,
+}
+
+/// A potion that sets a status on the hero for a while (Rogue's `PACT`).
+struct PotionAction {
+    flag: i32,
+    effect: Effect,
+    time: i32,
+    /// Message while hallucinating.
+    high: &'static str,
+    /// Message otherwise.
+    straight: &'static str,
+}
+
+/// The timed action of a potion, for those that have one (Rogue's
+/// `p_actions`). Seeing invisible has its own message.
+fn potion_action(which: i32) -> Option<PotionAction> {
+    let action = match which {
+        P_CONFUSE => PotionAction {
+            flag: IS_HUH,
+            effect: Effect::Unconfuse,
+            time: HUHDURATION,
+            high: "What a tripy feeling!",
+            straight: "Wait, what's going on here. Huh? What? Who?",
+        },
+        P_LSD => PotionAction {
+            flag: IS_HALU,
+            effect: Effect::ComeDown,
+            time: SEEDURATION,
+            high: "Oh, wow!  Everything seems so cosmic!",
+            straight: "Oh, wow!  Everything seems so cosmic!",
+        },
+        P_SEEINVIS => PotionAction {
+            flag: CAN_SEE,
+            effect: Effect::Unsee,
+            time: SEEDURATION,
+            high: "",
+            straight: "",
+        },
+        P_BLIND => PotionAction {
+            flag: IS_BLIND,
+            effect: Effect::Sight,
+            time: SEEDURATION,
+            high: "Oh, bummer!  Everything is dark!  Help!",
+            straight: "A cloak of darkness falls around you",
+        },
+        P_LEVIT => PotionAction {
+            flag: IS_LEVIT,
+            effect: Effect::Land,
+            time: LEVITDURATION,
+            high: "Oh, wow!  You're floating in the air!",
+            straight: "You start to float in the air",
+        },
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Whether an object is magical, for magic detection (Rogue's `is_magic`).
+pub fn is_magic(obj: &ObjectThing) -> bool {
+    match obj.kind {
+        ARMOR => {
+            obj.on(IS_PROT)
+                || A_CLASS
+                    .get(obj.which as usize)
+                    .is_some_and(|&a| obj.armor != a)
+        }
+        WEAPON => obj.hplus != 0 || obj.dplus != 0,
+        POTION | SCROLL | STICK | RING | AMULET => true,
+        _ => false,
+    }
+}
+
+impl GameState {
+    /// Pick a message for the hero's state of mind (Rogue's `choose_str`).
+    pub(crate) fn choose_str<'a>(&self, high: &'a str, straight: &'a str) -> &'a str {
+        if self.player.creature.on(IS_HALU) {
+            high
+        } else {
+            straight
+        }
+    }
+
+    /// Set a timed status from a potion, or make it last longer if the hero
+    /// already has it (Rogue's `do_pot`).
+    fn do_pot(&mut self, which: i32, knowit: bool, msg: &str) {
+        let Some(pa) = potion_action(which) else {
+            return;
+        };
+        if knowit {
+            self.knowledge.identify(POTION, which);
+        }
+        let t = spread(&mut self.rng, pa.time);
+        if !self.player.creature.on(pa.flag) {
+            self.player.creature.flags |= pa.flag;
+            self.daemons.fuse(pa.effect, 0, t, Phase::After);
+        } else {
+            self.daemons.lengthen(pa.effect, t);
+        }
+        let msg = if msg.is_empty() {
+            self.choose_str(pa.high, pa.straight).to_string()
+        } else {
+            msg.to_string()
+        };
+        self.messages.push(msg);
+    }
+
+    /// Speed the hero up, or make a hero already hasted faint (Rogue's
+    /// `add_haste`). Returns true if the hero is now hasted.
+    pub fn add_haste(&mut self, potion: bool) -> bool {
+        if self.player.creature.on(IS_HASTE) {
+            self.player.no_command += rnd(&mut self.rng, 8);
+            self.player.creature.flags &= !(IS_RUN | IS_HASTE);
+            self.daemons.extinguish(Effect::Nohaste);
+            self.messages
+                .push(String::from("You faint from exhaustion"));
+            return false;
+        }
+        self.player.creature.flags |= IS_HASTE;
+        if potion {
+            let time = rnd(&mut self.rng, 4) + 4;
+            self.daemons.fuse(Effect::Nohaste, 0, time, Phase::After);
+        }
+        true
+    }
+
+    /// Cure blindness (Rogue's `sight`).
+    fn sight(&mut self) {
+        self.run_effect(Effect::Sight, 0);
+    }
+
+    /// End hallucination (Rogue's `come_down`).
+    fn come_down(&mut self) {
+        self.run_effect(Effect::ComeDown, 0);
+    }
+
+    /// Every magical thing on the level, lying about or carried by
+    /// monsters.
+    fn magic_spots(&self) -> Vec<IVec2> {
+        let level = &self.dungeon.level;
+        let lying = level.objects.iter().filter(|o| is_magic(o)).map(|o| o.pos);
+        let carried = level.monsters.iter().filter_map(|m| {
+            m.pack
+                .iter()
+                .any(|t| matches!(t, Thing::Object(o) if is_magic(o)))
+                .then_some(m.pos)
+        });
+        lying.chain(carried).collect()
+    }
+
+    /// Drink the potion under letter `ch` (Rogue's `quaff`). Returns false
+    /// if no time passed.
+    pub fn quaff(&mut self, ch: char) -> bool {
+        let Some(kind) = self.player.pack.get(ch).map(|o| o.kind) else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        if kind != POTION {
+            self.messages
+                .push(String::from("Yuk! Why would you want to drink that?"));
+            return false;
+        }
+        let Some(obj) = self.player.pack.leave(ch, false) else {
+            return false;
+        };
+        let trip = self.player.creature.on(IS_HALU);
+        let mut after = true;
+
+        match obj.which {
+            P_CONFUSE => self.do_pot(P_CONFUSE, !trip, ""),
+            P_POISON => {
+                self.knowledge.identify(POTION, P_POISON);
+                if self.player.is_wearing(R_SUSTSTR) {
+                    self.messages
+                        .push(String::from("You feel momentarily sick"));
+                } else {
+                    let amt = rnd(&mut self.rng, 3) + 1;
+                    self.player.chg_str(-amt);
+                    self.messages.push(String::from("You feel very sick now"));
+                    self.come_down();
+                }
+            }
+            P_HEALING => {
+                self.knowledge.identify(POTION, P_HEALING);
+                let stats = &mut self.player.creature.stats;
+                stats.hp += roll(&mut self.rng, stats.level, 4);
+                if stats.hp > stats.max_hp {
+                    stats.max_hp += 1;
+                    stats.hp = stats.max_hp;
+                }
+                self.sight();
+                self.messages.push(String::from("You begin to feel better"));
+            }
+            P_STRENGTH => {
+                self.knowledge.identify(POTION, P_STRENGTH);
+                self.player.chg_str(1);
+                self.messages.push(String::from(
+                    "You feel stronger, now.  What bulging muscles!",
+                ));
+            }
+            P_MFIND => {
+                self.player.creature.flags |= SEE_MONST;
+                self.daemons
+                    .fuse(Effect::TurnSee, 0, HUHDURATION, Phase::After);
+                if self.dungeon.level.monsters.is_empty() {
+                    let msg = format!(
+                        "You have a {} feeling for a moment, then it passes",
+                        self.choose_str("normal", "strange")
+                    );
+                    self.messages.push(msg);
+                }
+            }
+            P_TFIND => {
+                let positions = self.magic_spots();
+                if positions.is_empty() {
+                    let msg = format!(
+                        "You have a {} feeling for a moment, then it passes",
+                        self.choose_str("normal", "strange")
+                    );
+                    self.messages.push(msg);
+                } else {
+                    self.knowledge.identify(POTION, P_TFIND);
+                    self.messages.push(String::from(
+                        "You sense the presence of magic on this level.",
+                    ));
+                    self.detection = Some(Detection {
+                        class: TerrainCellClass::Magic,
+                        positions,
+                    });
+                }
+            }
+            P_LSD => self.do_pot(P_LSD, true, ""),
+            P_SEEINVIS => {
+                let msg = format!("This potion tastes like {} juice", self.options.fruit);
+                self.do_pot(P_SEEINVIS, false, &msg);
+                self.sight();
+            }
+            P_RAISE => {
+                self.knowledge.identify(POTION, P_RAISE);
+                self.messages
+                    .push(String::from("You suddenly feel much more skillful"));
+                if let Some(level) = self.player.raise_level(&mut self.rng) {
+                    self.messages.push(format!("Welcome to level {}", level));
+                }
+            }
+            P_XHEAL => {
+                self.knowledge.identify(POTION, P_XHEAL);
+                let stats = &mut self.player.creature.stats;
+                stats.hp += roll(&mut self.rng, stats.level, 8);
+                if stats.hp > stats.max_hp {
+                    if stats.hp > stats.max_hp + stats.level + 1 {
+                        stats.max_hp += 1;
+                    }
+                    stats.max_hp += 1;
+                    stats.hp = stats.max_hp;
+                }
+                self.sight();
+                self.come_down();
+                self.messages
+                    .push(String::from("You begin to feel much better"));
+            }
+            P_HASTE => {
+                self.knowledge.identify(POTION, P_HASTE);
+                after = false;
+                if self.add_haste(true) {
+                    self.messages
+                        .push(String::from("You feel yourself moving much faster"));
+                }
+            }
+            P_RESTORE => {
+                self.player.restore_strength();
+                self.messages.push(String::from(
+                    "Hey, this tastes great.  It make you feel warm all over",
+                ));
+            }
+            P_BLIND => self.do_pot(P_BLIND, true, ""),
+            P_LEVIT => self.do_pot(P_LEVIT, true, ""),
+            _ => {}
+        }
+        after
+    }
+}
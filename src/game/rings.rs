@@ -0,0 +1,204 @@
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::game::daemon::Effect;
+use crate::game::game_state::GameState;
+use crate::game::player::{add_str, Player};
+use crate::thing::fight::{save_throw, RingBonus};
+use crate::thing::object_table::{
+    MAX_RINGS, R_ADDDAM, R_ADDHIT, R_ADDSTR, R_AGGR, R_DIGEST, R_PROTECT, R_SEARCH, R_SEEINVIS,
+    R_TELEPORT,
+};
+use crate::thing::{ObjectThing, CAN_SEE, RING};
+
+/* Hands a ring can be worn on. */
+pub const LEFT: usize = 0;
+pub const RIGHT: usize = 1;
+
+/// Food each kind of ring uses up a turn; a negative number means one in
+/// that many turns (Rogue's `uses` in `ring_eat`).
+const RING_USES: [i32; MAX_RINGS] = [1, 1, 1, -3, -5, 0, 0, -3, -3, 2, -2, 0, 1, 1];
+
+impl Player {
+    /// The ring on a hand, if any.
+    pub fn ring(&self, hand: usize) -> Option<&ObjectThing> {
+        self.cur_ring[hand].and_then(|ch| self.pack.get(ch))
+    }
+
+    /// Whether the ring on a hand is of a kind (Rogue's `ISRING`).
+    pub fn is_ring(&self, hand: usize, which: i32) -> bool {
+        self.ring(hand).is_some_and(|r| r.which == which)
+    }
+
+    /// Whether either hand has a ring of a kind (Rogue's `ISWEARING`).
+    pub fn is_wearing(&self, which: i32) -> bool {
+        self.is_ring(LEFT, which) || self.is_ring(RIGHT, which)
+    }
+
+    /// The hand an object in the pack is worn on, if it is a ring being
+    /// worn.
+    pub fn hand_of(&self, obj: &ObjectThing) -> Option<usize> {
+        [LEFT, RIGHT]
+            .into_iter()
+            .find(|&hand| self.ring(hand).is_some_and(|r| std::ptr::eq(r, obj)))
+    }
+
+    /// Total enchantment of the rings of a kind being worn.
+    pub fn ring_sum(&self, which: i32) -> i32 {
+        [LEFT, RIGHT]
+            .into_iter()
+            .filter_map(|hand| self.ring(hand))
+            .filter(|r| r.which == which)
+            .map(|r| r.armor)
+            .sum()
+    }
+
+    /// How much more food the ring on a hand eats this turn; a ring of
+    /// slow digestion gives some back (Rogue's `ring_eat`).
+    pub fn ring_eat<R: Rng + ?Sized>(&self, hand: usize, rng: &mut R) -> i32 {
+        let Some(ring) = self.ring(hand) else {
+            return 0;
+        };
+        let mut eat = RING_USES.get(ring.which as usize).copied().unwrap_or(0);
+        if eat < 0 {
+            eat = (rnd(rng, -eat) == 0) as i32;
+        }
+        if ring.which == R_DIGEST {
+            eat = -eat;
+        }
+        eat
+    }
+
+    /// To-hit and damage the hero's rings add to a blow.
+    pub fn ring_bonus(&self) -> RingBonus {
+        RingBonus {
+            hplus: self.ring_sum(R_ADDHIT),
+            dplus: self.ring_sum(R_ADDDAM),
+        }
+    }
+
+    /// See if the hero saves against something, rings of protection
+    /// helping (Rogue's `save`).
+    pub fn save<R: Rng + ?Sized>(&self, which: i32, rng: &mut R) -> bool {
+        save_throw(which - self.ring_sum(R_PROTECT), self.stats(), rng)
+    }
+
+    /// Bring strength back up to its best, keeping what rings of strength
+    /// add on top.
+    pub fn restore_strength(&mut self) {
+        let rings = self.ring_sum(R_ADDSTR);
+        let stats = &mut self.creature.stats;
+        stats.strength = add_str(stats.strength, -rings).max(self.max_strength);
+        stats.strength = add_str(stats.strength, rings);
+    }
+}
+
+impl GameState {
+    /// Put on the ring under letter `ch` (Rogue's `ring_on`). With both
+    /// hands free it goes on `hand`, or the left one; otherwise on the free
+    /// hand. Returns false if no ring went on.
+    pub fn ring_on(&mut self, ch: char, hand: Option<usize>) -> bool {
+        let Some((kind, which, arm)) = self.player.pack.get(ch).map(|o| (o.kind, o.which, o.armor))
+        else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        if kind != RING {
+            let msg = if self.options.terse {
+                "Not a ring"
+            } else {
+                "It would be difficult to wrap that around a finger"
+            };
+            self.messages.push(String::from(msg));
+            return false;
+        }
+        if self.is_current(ch) {
+            return false;
+        }
+        let hand = match self.player.cur_ring {
+            [None, None] => hand.unwrap_or(LEFT).min(RIGHT),
+            [None, _] => LEFT,
+            [_, None] => RIGHT,
+            _ => {
+                let msg = if self.options.terse {
+                    "Wearing two"
+                } else {
+                    "You already have a ring on each hand"
+                };
+                self.messages.push(String::from(msg));
+                return false;
+            }
+        };
+
+        self.player.cur_ring[hand] = Some(ch);
+        match which {
+            R_ADDSTR => self.player.chg_str(arm),
+            R_SEEINVIS => self.player.creature.flags |= CAN_SEE,
+            R_AGGR => self.aggravate(),
+            _ => {}
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let msg = if self.options.terse {
+                format!("{} ({})", self.inv_name(obj, false), ch)
+            } else {
+                format!("You are now wearing {} ({})", self.inv_name(obj, true), ch)
+            };
+            self.messages.push(msg);
+        }
+        true
+    }
+
+    /// Take off a ring (Rogue's `ring_off`). With a ring on each hand it
+    /// comes off `hand`, or the left one. Returns false if none came off.
+    pub fn ring_off(&mut self, hand: Option<usize>) -> bool {
+        let hand = match self.player.cur_ring {
+            [None, None] => {
+                let msg = if self.options.terse {
+                    "No rings"
+                } else {
+                    "You aren't wearing any rings"
+                };
+                self.messages.push(String::from(msg));
+                return false;
+            }
+            [None, _] => RIGHT,
+            [_, None] => LEFT,
+            _ => hand.unwrap_or(LEFT).min(RIGHT),
+        };
+        let Some(ch) = self.player.cur_ring[hand] else {
+            return false;
+        };
+        if !self.dropcheck(ch) {
+            return false;
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let msg = format!("Was wearing {}({})", self.inv_name(obj, true), ch);
+            self.messages.push(msg);
+        }
+        true
+    }
+
+    /// Undo what a ring did while it was worn, as it comes off.
+    pub(crate) fn ring_removed(&mut self, which: i32, arm: i32) {
+        match which {
+            R_ADDSTR => self.player.chg_str(-arm),
+            R_SEEINVIS => {
+                self.run_effect(Effect::Unsee, 0);
+                self.daemons.extinguish(Effect::Unsee);
+            }
+            _ => {}
+        }
+    }
+
+    /// What rings of searching and teleportation do each turn the hero
+    /// gets to act (from Rogue's `command`).
+    pub fn ring_turn(&mut self) {
+        for hand in [LEFT, RIGHT] {
+            if self.player.is_ring(hand, R_SEARCH) {
+                self.search();
+            } else if self.player.is_ring(hand, R_TELEPORT) && rnd(&mut self.rng, 50) == 0 {
+                self.teleport();
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::game_state::GameState;
+use crate::game::save_codec::{SaveReader, SaveWriter};
+
+/// First bytes of every save file.
+pub const SAVE_MAGIC: [u8; 8] = *b"XEGUESAV";
+
+/// Version of the save format. Bump it whenever the saved game state
+/// changes shape; older files are then refused instead of misread.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Size of the header: magic, version, payload length and checksum.
+const HEADER_LEN: usize = 8 + 4 + 8 + 8;
+
+/// Errors from saving or restoring a game.
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    /// The file does not start with `SAVE_MAGIC`.
+    NotASaveFile,
+    /// The file was written by a different save format version.
+    Version {
+        found: u32,
+        expected: u32,
+    },
+    /// The payload does not match the checksum in the header.
+    Checksum,
+    /// The file ends before the data it promises.
+    Truncated,
+    /// The payload decodes to something that cannot be a game.
+    Corrupt(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "save file error: {}", err),
+            SaveError::NotASaveFile => write!(f, "not a xegue save file"),
+            SaveError::Version { found, expected } => write!(
+                f,
+                "save file is version {}, this game reads version {}",
+                found, expected
+            ),
+            SaveError::Checksum => write!(f, "save file is corrupted (checksum mismatch)"),
+            SaveError::Truncated => write!(f, "save file is truncated"),
+            SaveError::Corrupt(why) => write!(f, "save file is corrupted: {}", why),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+/// How to treat the save file when restoring from it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RestoreOptions {
+    /// Delete the file once the game is restored, so a game can only be
+    /// resumed once (Rogue's one-save-per-game rule).
+    pub delete_save: bool,
+}
+
+/// 64-bit FNV-1a hash, used as the payload checksum.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Encode a game into the bytes of a save file.
+pub fn encode_game(game: &GameState) -> Vec<u8> {
+    let mut payload = SaveWriter::default();
+    payload.put(game);
+
+    let mut w = SaveWriter::default();
+    w.bytes.extend_from_slice(&SAVE_MAGIC);
+    w.put_u32(SAVE_VERSION);
+    w.put_usize(payload.bytes.len());
+    w.put_u64(checksum(&payload.bytes));
+    w.bytes.extend_from_slice(&payload.bytes);
+    w.bytes
+}
+
+/// Decode the bytes of a save file, checking the header first.
+pub fn decode_game(bytes: &[u8]) -> Result<GameState, SaveError> {
+    if bytes.len() < SAVE_MAGIC.len() || bytes[..SAVE_MAGIC.len()] != SAVE_MAGIC {
+        return Err(SaveError::NotASaveFile);
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(SaveError::Truncated);
+    }
+
+    let mut header = SaveReader::new(&bytes[SAVE_MAGIC.len()..HEADER_LEN]);
+    let version = header.get_u32()?;
+    if version != SAVE_VERSION {
+        return Err(SaveError::Version {
+            found: version,
+            expected: SAVE_VERSION,
+        });
+    }
+    let len = header.get_usize()?;
+    let sum = header.get_u64()?;
+
+    let payload = &bytes[HEADER_LEN..];
+    if payload.len() < len {
+        return Err(SaveError::Truncated);
+    }
+    if payload.len() > len {
+        return Err(SaveError::Corrupt("trailing data after the game".into()));
+    }
+    if checksum(payload) != sum {
+        return Err(SaveError::Checksum);
+    }
+
+    let mut r = SaveReader::new(payload);
+    let game = r.get()?;
+    if !r.is_at_end() {
+        return Err(SaveError::Corrupt("game ends before the payload".into()));
+    }
+    Ok(game)
+}
+
+/// Save the game to `path` (Rogue's `save_file`).
+///
+/// The file is written next to its destination first and then renamed, so
+/// a failed save never leaves a half-written file behind.
+pub fn save_game(game: &GameState, path: &Path) -> Result<(), SaveError> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, encode_game(game))?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Restore a saved game from `path` (Rogue's `restore`).
+pub fn restore_game(path: &Path, options: RestoreOptions) -> Result<GameState, SaveError> {
+    let game = decode_game(&fs::read(path)?)?;
+    if options.delete_save {
+        fs::remove_file(path)?;
+    }
+    Ok(game)
+}
@@ -0,0 +1,766 @@
+use glam::IVec2;
+
+use crate::dungeon_level::dungeon::Dungeon;
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::game::daemon::{DelayedAction, Effect, Phase, Scheduler};
+use crate::game::game_state::GameState;
+use crate::game::hunger::HungerState;
+use crate::game::options::Options;
+use crate::game::player::Player;
+use crate::game::save::SaveError;
+use crate::rng::GameRng;
+use crate::terminal::terminal_symbol::TerminalSymbol;
+use crate::terrain::pathfinding::Pathfinder;
+use crate::terrain::terrain_cell::TerrainCell;
+use crate::terrain::terrain_grid::TerrainGrid;
+use crate::terrain_structure::passage::Passage;
+use crate::terrain_structure::room::{Room, RoomKind};
+use crate::thing::knowledge::{Knowledge, TypeKnowledge};
+use crate::thing::new_thing::ThingCounters;
+use crate::thing::pack::Pack;
+use crate::thing::stats::Stats;
+use crate::thing::{MonsterThing, ObjectThing, Thing};
+
+/// Little-endian byte writer for save files (Rogue's `rs_write_*`).
+#[derive(Default)]
+pub struct SaveWriter {
+    pub bytes: Vec<u8>,
+}
+
+impl SaveWriter {
+    pub fn put_u8(&mut self, v: u8) {
+        self.bytes.push(v);
+    }
+
+    pub fn put_bool(&mut self, v: bool) {
+        self.put_u8(v as u8);
+    }
+
+    pub fn put_u32(&mut self, v: u32) {
+        self.bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub fn put_i32(&mut self, v: i32) {
+        self.bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub fn put_u64(&mut self, v: u64) {
+        self.bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub fn put_usize(&mut self, v: usize) {
+        self.put_u64(v as u64);
+    }
+
+    pub fn put_char(&mut self, v: char) {
+        self.put_u32(v as u32);
+    }
+
+    pub fn put_str(&mut self, v: &str) {
+        self.put_usize(v.len());
+        self.bytes.extend_from_slice(v.as_bytes());
+    }
+
+    pub fn put_ivec2(&mut self, v: IVec2) {
+        self.put_i32(v.x);
+        self.put_i32(v.y);
+    }
+
+    pub fn put<T: SaveData>(&mut self, v: &T) {
+        v.write(self);
+    }
+
+    pub fn put_option<T: SaveData>(&mut self, v: &Option<T>) {
+        self.put_bool(v.is_some());
+        if let Some(v) = v {
+            v.write(self);
+        }
+    }
+
+    pub fn put_vec<T: SaveData>(&mut self, v: &[T]) {
+        self.put_usize(v.len());
+        for item in v {
+            item.write(self);
+        }
+    }
+}
+
+/// Reader matching `SaveWriter`; every read fails cleanly on short input
+/// (Rogue's `rs_read_*`).
+pub struct SaveReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SaveReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Whether every byte has been consumed.
+    pub fn is_at_end(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SaveError> {
+        let end = self.pos.checked_add(n).ok_or(SaveError::Truncated)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(SaveError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8, SaveError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn get_bool(&mut self) -> Result<bool, SaveError> {
+        match self.get_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            v => Err(SaveError::Corrupt(format!("bad boolean {}", v))),
+        }
+    }
+
+    pub fn get_u32(&mut self) -> Result<u32, SaveError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn get_i32(&mut self) -> Result<i32, SaveError> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn get_u64(&mut self) -> Result<u64, SaveError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn get_usize(&mut self) -> Result<usize, SaveError> {
+        let v = self.get_u64()?;
+        usize::try_from(v).map_err(|_| SaveError::Corrupt(format!("length {} too large", v)))
+    }
+
+    pub fn get_char(&mut self) -> Result<char, SaveError> {
+        let v = self.get_u32()?;
+        char::from_u32(v).ok_or_else(|| SaveError::Corrupt(format!("bad character {:#x}", v)))
+    }
+
+    pub fn get_str(&mut self) -> Result<String, SaveError> {
+        let len = self.get_usize()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| SaveError::Corrupt("bad string".into()))
+    }
+
+    pub fn get_ivec2(&mut self) -> Result<IVec2, SaveError> {
+        Ok(IVec2::new(self.get_i32()?, self.get_i32()?))
+    }
+
+    pub fn get<T: SaveData>(&mut self) -> Result<T, SaveError> {
+        T::read(self)
+    }
+
+    pub fn get_option<T: SaveData>(&mut self) -> Result<Option<T>, SaveError> {
+        if self.get_bool()? {
+            Ok(Some(T::read(self)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get_vec<T: SaveData>(&mut self) -> Result<Vec<T>, SaveError> {
+        let len = self.get_usize()?;
+        // Every element takes at least one byte, which bounds bogus lengths.
+        if len > self.bytes.len() - self.pos {
+            return Err(SaveError::Truncated);
+        }
+        (0..len).map(|_| T::read(self)).collect()
+    }
+}
+
+/// A value that can be written to and read back from a save file.
+pub trait SaveData: Sized {
+    fn write(&self, w: &mut SaveWriter);
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError>;
+}
+
+impl SaveData for String {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_str(self);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.get_str()
+    }
+}
+
+impl SaveData for IVec2 {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_ivec2(*self);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.get_ivec2()
+    }
+}
+
+impl SaveData for char {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_char(*self);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.get_char()
+    }
+}
+
+impl SaveData for i32 {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_i32(*self);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.get_i32()
+    }
+}
+
+impl SaveData for usize {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_usize(*self);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        r.get_usize()
+    }
+}
+
+impl SaveData for Stats {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_i32(self.strength);
+        w.put_i32(self.exp);
+        w.put_i32(self.level);
+        w.put_i32(self.armor);
+        w.put_i32(self.hp);
+        w.put_str(&self.damage);
+        w.put_i32(self.max_hp);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Stats {
+            strength: r.get_i32()?,
+            exp: r.get_i32()?,
+            level: r.get_i32()?,
+            armor: r.get_i32()?,
+            hp: r.get_i32()?,
+            damage: r.get_str()?,
+            max_hp: r.get_i32()?,
+        })
+    }
+}
+
+impl SaveData for ObjectThing {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_i32(self.kind);
+        w.put_ivec2(self.pos);
+        w.put_option(&self.text);
+        w.put_i32(self.launch);
+        w.put_char(self.pack_ch);
+        w.put_str(&self.damage);
+        w.put_str(&self.hurl_dmg);
+        w.put_i32(self.count);
+        w.put_i32(self.which);
+        w.put_i32(self.hplus);
+        w.put_i32(self.dplus);
+        w.put_i32(self.armor);
+        w.put_i32(self.flags);
+        w.put_i32(self.group);
+        w.put_option(&self.label);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(ObjectThing {
+            kind: r.get_i32()?,
+            pos: r.get_ivec2()?,
+            text: r.get_option()?,
+            launch: r.get_i32()?,
+            pack_ch: r.get_char()?,
+            damage: r.get_str()?,
+            hurl_dmg: r.get_str()?,
+            count: r.get_i32()?,
+            which: r.get_i32()?,
+            hplus: r.get_i32()?,
+            dplus: r.get_i32()?,
+            armor: r.get_i32()?,
+            flags: r.get_i32()?,
+            group: r.get_i32()?,
+            label: r.get_option()?,
+        })
+    }
+}
+
+impl SaveData for MonsterThing {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_ivec2(self.pos);
+        w.put_bool(self.turn);
+        w.put_char(self.kind);
+        w.put_char(self.disguise);
+        w.put_char(self.old_ch);
+        w.put_option(&self.dest);
+        w.put_i32(self.flags);
+        w.put(&self.stats);
+        w.put_option(&self.room_id);
+        w.put_vec(&self.pack);
+        w.put_i32(self.reserved);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(MonsterThing {
+            pos: r.get_ivec2()?,
+            turn: r.get_bool()?,
+            kind: r.get_char()?,
+            disguise: r.get_char()?,
+            old_ch: r.get_char()?,
+            dest: r.get_option()?,
+            flags: r.get_i32()?,
+            stats: r.get()?,
+            room_id: r.get_option()?,
+            pack: r.get_vec()?,
+            reserved: r.get_i32()?,
+        })
+    }
+}
+
+impl SaveData for Thing {
+    fn write(&self, w: &mut SaveWriter) {
+        match self {
+            Thing::Monster(monster) => {
+                w.put_u8(0);
+                w.put(monster);
+            }
+            Thing::Object(object) => {
+                w.put_u8(1);
+                w.put(object);
+            }
+        }
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.get_u8()? {
+            0 => Ok(Thing::Monster(r.get()?)),
+            1 => Ok(Thing::Object(r.get()?)),
+            v => Err(SaveError::Corrupt(format!("bad thing tag {}", v))),
+        }
+    }
+}
+
+impl SaveData for TerrainCell {
+    fn write(&self, w: &mut SaveWriter) {
+        // Cell classes are stored as the character they are drawn with.
+        w.put_char(TerminalSymbol::char_from_terrain_cell_class(
+            self.cell_class,
+        ));
+        w.put_u8(self.flags);
+        w.put_option(&self.monst);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let ch = r.get_char()?;
+        let symbol = TerminalSymbol::from_char(ch)
+            .ok_or_else(|| SaveError::Corrupt(format!("bad map character {:?}", ch)))?;
+        Ok(TerrainCell::new(
+            symbol.to_terrain_cell_class(),
+            r.get_u8()?,
+            r.get_option()?,
+        ))
+    }
+}
+
+impl SaveData for TerrainGrid {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_usize(self.width);
+        w.put_usize(self.height);
+        w.put_vec(&self.cells);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        let width = r.get_usize()?;
+        let height = r.get_usize()?;
+        let cells: Vec<TerrainCell> = r.get_vec()?;
+        if Some(cells.len()) != width.checked_mul(height) {
+            return Err(SaveError::Corrupt(
+                "map size does not match its cells".into(),
+            ));
+        }
+        Ok(TerrainGrid {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl SaveData for Room {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_ivec2(self.pos);
+        w.put_ivec2(self.size);
+        w.put_bool(self.is_maze);
+        w.put_bool(self.is_gone);
+        w.put_bool(self.is_dark);
+        w.put_vec(&self.doors);
+        w.put_u8(match self.kind {
+            RoomKind::Normal => 0,
+            RoomKind::Treasure => 1,
+        });
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Room {
+            pos: r.get_ivec2()?,
+            size: r.get_ivec2()?,
+            is_maze: r.get_bool()?,
+            is_gone: r.get_bool()?,
+            is_dark: r.get_bool()?,
+            doors: r.get_vec()?,
+            kind: match r.get_u8()? {
+                0 => RoomKind::Normal,
+                1 => RoomKind::Treasure,
+                v => return Err(SaveError::Corrupt(format!("bad room kind {}", v))),
+            },
+        })
+    }
+}
+
+impl SaveData for Passage {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_usize(self.room_a);
+        w.put_usize(self.room_b);
+        w.put_ivec2(self.door_a);
+        w.put_ivec2(self.door_b);
+        w.put_vec(&self.tiles);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Passage::new(
+            r.get_usize()?,
+            r.get_usize()?,
+            r.get_ivec2()?,
+            r.get_ivec2()?,
+            r.get_vec()?,
+        ))
+    }
+}
+
+impl SaveData for DungeonLevel {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put(&self.terrain);
+        w.put_vec(&self.rooms);
+        w.put_vec(&self.passages);
+        w.put_vec(&self.objects);
+        w.put_vec(&self.monsters);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(DungeonLevel {
+            terrain: r.get()?,
+            rooms: r.get_vec()?,
+            passages: r.get_vec()?,
+            objects: r.get_vec()?,
+            monsters: r.get_vec()?,
+        })
+    }
+}
+
+impl SaveData for Dungeon {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_i32(self.depth);
+        w.put_i32(self.max_depth);
+        w.put(&self.level);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Dungeon {
+            depth: r.get_i32()?,
+            max_depth: r.get_i32()?,
+            level: r.get()?,
+        })
+    }
+}
+
+impl SaveData for ThingCounters {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_i32(self.no_food);
+        w.put_i32(self.group);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(ThingCounters {
+            no_food: r.get_i32()?,
+            group: r.get_i32()?,
+        })
+    }
+}
+
+impl SaveData for HungerState {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_u8(*self as u8);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.get_u8()? {
+            0 => Ok(HungerState::NotHungry),
+            1 => Ok(HungerState::Hungry),
+            2 => Ok(HungerState::Weak),
+            3 => Ok(HungerState::Faint),
+            n => Err(SaveError::Corrupt(format!("bad hunger state {}", n))),
+        }
+    }
+}
+
+impl SaveData for Pack {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_vec(&self.items);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Pack {
+            items: r.get_vec()?,
+        })
+    }
+}
+
+impl SaveData for Player {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put(&self.creature);
+        w.put_i32(self.max_strength);
+        w.put(&self.pack);
+        w.put_i32(self.purse);
+        w.put_i32(self.quiet);
+        w.put_i32(self.food_left);
+        w.put(&self.hungry_state);
+        w.put_i32(self.no_command);
+        w.put_option(&self.cur_weapon);
+        w.put_option(&self.cur_armor);
+        w.put_option(&self.cur_ring[0]);
+        w.put_option(&self.cur_ring[1]);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Player {
+            creature: r.get()?,
+            max_strength: r.get_i32()?,
+            pack: r.get()?,
+            purse: r.get_i32()?,
+            quiet: r.get_i32()?,
+            food_left: r.get_i32()?,
+            hungry_state: r.get()?,
+            no_command: r.get_i32()?,
+            cur_weapon: r.get_option()?,
+            cur_armor: r.get_option()?,
+            cur_ring: [r.get_option()?, r.get_option()?],
+        })
+    }
+}
+
+impl SaveData for Phase {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_u8(*self as u8);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        match r.get_u8()? {
+            0 => Ok(Phase::Before),
+            1 => Ok(Phase::After),
+            n => Err(SaveError::Corrupt(format!("bad daemon phase {}", n))),
+        }
+    }
+}
+
+impl SaveData for Effect {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_u8(*self as u8);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        const EFFECTS: [Effect; 12] = [
+            Effect::Doctor,
+            Effect::Stomach,
+            Effect::Runners,
+            Effect::Swander,
+            Effect::Rollwand,
+            Effect::Unconfuse,
+            Effect::Unsee,
+            Effect::Sight,
+            Effect::Nohaste,
+            Effect::ComeDown,
+            Effect::Land,
+            Effect::TurnSee,
+        ];
+        let n = r.get_u8()?;
+        EFFECTS
+            .get(n as usize)
+            .copied()
+            .ok_or_else(|| SaveError::Corrupt(format!("bad daemon effect {}", n)))
+    }
+}
+
+impl SaveData for DelayedAction {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put(&self.phase);
+        w.put(&self.effect);
+        w.put_i32(self.arg);
+        w.put_i32(self.time);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(DelayedAction {
+            phase: r.get()?,
+            effect: r.get()?,
+            arg: r.get_i32()?,
+            time: r.get_i32()?,
+        })
+    }
+}
+
+impl SaveData for Scheduler {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_vec(&self.actions);
+        w.put_i32(self.between);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Scheduler {
+            actions: r.get_vec()?,
+            between: r.get_i32()?,
+        })
+    }
+}
+
+impl SaveData for TypeKnowledge {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_bool(self.know);
+        w.put_option(&self.guess);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(TypeKnowledge {
+            know: r.get_bool()?,
+            guess: r.get_option()?,
+        })
+    }
+}
+
+impl SaveData for Knowledge {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_vec(&self.p_colors);
+        w.put_vec(&self.s_names);
+        w.put_vec(&self.r_stones);
+        w.put_vec(&self.r_worth);
+        w.put_vec(&self.ws_made);
+        w.put_vec(&self.ws_type);
+        w.put_vec(&self.potions);
+        w.put_vec(&self.scrolls);
+        w.put_vec(&self.rings);
+        w.put_vec(&self.sticks);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Knowledge {
+            p_colors: r.get_vec()?,
+            s_names: r.get_vec()?,
+            r_stones: r.get_vec()?,
+            r_worth: r.get_vec()?,
+            ws_made: r.get_vec()?,
+            ws_type: r.get_vec()?,
+            potions: r.get_vec()?,
+            scrolls: r.get_vec()?,
+            rings: r.get_vec()?,
+            sticks: r.get_vec()?,
+        })
+    }
+}
+
+impl SaveData for Options {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_bool(self.terse);
+        w.put_str(&self.fruit);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(Options {
+            terse: r.get_bool()?,
+            fruit: r.get_str()?,
+        })
+    }
+}
+
+impl SaveData for GameState {
+    fn write(&self, w: &mut SaveWriter) {
+        w.put_u32(self.rng.state());
+        w.put(&self.dungeon);
+        w.put(&self.player);
+        w.put(&self.counters);
+        w.put_vec(&self.messages);
+        w.put(&self.daemons);
+        w.put(&self.knowledge);
+        w.put(&self.options);
+    }
+
+    fn read(r: &mut SaveReader) -> Result<Self, SaveError> {
+        Ok(GameState {
+            rng: GameRng::new(r.get_u32()?),
+            dungeon: r.get()?,
+            player: r.get()?,
+            counters: r.get()?,
+            messages: r.get_vec()?,
+            daemons: r.get()?,
+            knowledge: r.get()?,
+            options: r.get()?,
+            detection: None,
+            actions_left: 0,
+            killer: None,
+            pathfinder: Pathfinder::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::save::{decode_game, encode_game};
+
+    /// Every effect the codec knows, in the order of their bytes.
+    fn saved_effects() -> Vec<Effect> {
+        (0..=u8::MAX)
+            .map_while(|n| SaveReader::new(&[n]).get::<Effect>().ok())
+            .collect()
+    }
+
+    #[test]
+    fn effects_round_trip() {
+        let effects = saved_effects();
+        // The last variant, so a new effect missing from `EFFECTS` fails.
+        assert_eq!(effects.len(), Effect::TurnSee as usize + 1);
+        for (n, effect) in effects.into_iter().enumerate() {
+            assert_eq!(effect as usize, n);
+            let mut w = SaveWriter::default();
+            w.put(&effect);
+            assert_eq!(w.bytes, [n as u8]);
+            assert_eq!(SaveReader::new(&w.bytes).get::<Effect>().ok(), Some(effect));
+        }
+    }
+
+    #[test]
+    fn game_round_trip() {
+        let mut game = GameState::new(42);
+        for (i, effect) in saved_effects().into_iter().enumerate() {
+            game.daemons
+                .fuse(effect, i as i32, 10 + i as i32, Phase::After);
+        }
+        game.messages.push(String::from("Hello"));
+
+        let bytes = encode_game(&game);
+        let restored = decode_game(&bytes).expect("the save decodes");
+        assert_eq!(restored.daemons, game.daemons);
+        assert_eq!(restored.knowledge, game.knowledge);
+        assert_eq!(restored.messages, game.messages);
+        assert_eq!(restored.player.pos(), game.player.pos());
+        assert_eq!(encode_game(&restored), bytes);
+    }
+}
@@ -0,0 +1,411 @@
+use glam::IVec2;
+
+use crate::dice::rnd;
+use crate::game::game_state::GameState;
+use crate::game::potions::Detection;
+use crate::game::rings::{LEFT, RIGHT};
+use crate::terrain::terrain_cell::{TerrainCellClass, F_PASS, F_SECRET, F_SEEN};
+use crate::thing::chase::{runto, scare_at};
+use crate::thing::knowledge::pick_color;
+use crate::thing::monster_table::{monster_info, new_monster, put_monster, rand_monster};
+use crate::thing::naming::vowelstr;
+use crate::thing::object_table::{
+    SCR_INFO, S_AGGR, S_ARMOR, S_CONFUSE, S_CREATE, S_ENCH, S_FDET, S_HOLD, S_ID_ARMOR,
+    S_ID_POTION, S_ID_R_OR_S, S_ID_SCROLL, S_ID_WEAPON, S_MAP, S_PROTECT, S_REMOVE, S_SCARE,
+    S_SLEEP, S_TELEP, WEAP_INFO,
+};
+use crate::thing::{
+    ARMOR, CAN_HUH, FOOD, IS_CURSED, IS_HALU, IS_HELD, IS_KNOW, IS_PROT, IS_RUN, POTION, RING,
+    SCROLL, STICK, WEAPON,
+};
+
+/* Turns, less four, the hero may sleep for from a scroll of sleep. */
+pub const SLEEPTIME: i32 = 5;
+
+/// What came of reading a scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadOutcome {
+    /// Nothing was read and no time passed.
+    NotRead,
+    /// The scroll was read and is gone.
+    Read,
+    /// An identify scroll was read; the player picks something of one of
+    /// these types for `whatis`.
+    Identify(&'static [i32]),
+}
+
+/// Types of object an identify scroll works on (Rogue's `id_type`).
+fn id_kinds(which: i32) -> &'static [i32] {
+    match which {
+        S_ID_POTION => &[POTION],
+        S_ID_SCROLL => &[SCROLL],
+        S_ID_WEAPON => &[WEAPON],
+        S_ID_ARMOR => &[ARMOR],
+        _ => &[RING, STICK],
+    }
+}
+
+/// What to call a set of types when asking for one (Rogue's `type_name`).
+fn kinds_name(kinds: &[i32]) -> &'static str {
+    match kinds {
+        [POTION] => "potion",
+        [SCROLL] => "scroll",
+        [WEAPON] => "weapon",
+        [ARMOR] => "armor",
+        [RING] => "ring",
+        [STICK] => "wand or staff",
+        _ => "ring, wand or staff",
+    }
+}
+
+impl GameState {
+    /// A color for a message, scrambled while hallucinating.
+    fn pick_color(&mut self, col: &'static str) -> &'static str {
+        pick_color(col, self.player.creature.on(IS_HALU), &mut self.rng)
+    }
+
+    /// Move the hero to a random spot on the level, breaking free of
+    /// anything holding on; a venus flytrap's grip starts over (Rogue's
+    /// `teleport`).
+    pub fn teleport(&mut self) {
+        let level = &self.dungeon.level;
+        let Some(pos) = level.find_floor(None, 0, true, &mut self.rng) else {
+            return;
+        };
+        if pos == self.player.pos() {
+            return;
+        }
+        let hero = &mut self.player.creature;
+        hero.pos = pos;
+        hero.room_id = level.room_at(pos);
+        if hero.on(IS_HELD) {
+            hero.flags &= !IS_HELD;
+            let damage = monster_info('F').stats.damage;
+            for tp in self
+                .dungeon
+                .level
+                .monsters
+                .iter_mut()
+                .filter(|m| m.kind == 'F')
+            {
+                tp.stats.damage = damage.to_string();
+            }
+        }
+    }
+
+    /// Set every monster on the level running at the hero (Rogue's
+    /// `aggravate`).
+    pub fn aggravate(&mut self) {
+        let positions: Vec<IVec2> = self.dungeon.level.monsters.iter().map(|m| m.pos).collect();
+        for pos in positions {
+            runto(
+                &mut self.dungeon.level,
+                pos,
+                &self.player.creature,
+                &mut self.rng,
+            );
+        }
+    }
+
+    /// Show the level's passages, doors, stairs and traps, hidden ones
+    /// included.
+    fn magic_map(&mut self) {
+        for cell in &mut self.dungeon.level.terrain.cells {
+            if cell.flags & F_SECRET != 0 {
+                cell.cell_class = if cell.flags & F_PASS != 0 {
+                    TerrainCellClass::Passage
+                } else {
+                    TerrainCellClass::Door
+                };
+            }
+            let shown = matches!(
+                cell.cell_class,
+                TerrainCellClass::Door
+                    | TerrainCellClass::Stairs
+                    | TerrainCellClass::Passage
+                    | TerrainCellClass::Trap
+            ) || cell.flags & F_PASS != 0;
+            if shown {
+                cell.flags &= !F_SECRET;
+                cell.flags |= F_SEEN;
+            }
+        }
+    }
+
+    /// Freeze the running monsters within two squares of the hero.
+    /// Returns how many were caught.
+    fn hold_monsters(&mut self) -> usize {
+        let hero = self.player.pos();
+        let mut held = 0;
+        for tp in &mut self.dungeon.level.monsters {
+            let d = (tp.pos - hero).abs();
+            if d.x <= 2 && d.y <= 2 && tp.on(IS_RUN) {
+                tp.flags &= !IS_RUN;
+                tp.flags |= IS_HELD;
+                held += 1;
+            }
+        }
+        held
+    }
+
+    /// Bring a monster in next to the hero, if there is anywhere to put
+    /// it. Returns whether one came.
+    fn create_monster(&mut self) -> bool {
+        let hero = self.player.pos();
+        let level = &self.dungeon.level;
+        let mut spots = 0;
+        let mut spot = None;
+        for y in hero.y - 1..=hero.y + 1 {
+            for x in hero.x - 1..=hero.x + 1 {
+                let pos = IVec2::new(x, y);
+                if pos == hero
+                    || level.monster_at(pos).is_some()
+                    || !level.cell_class_at(pos).is_some_and(|c| c.is_walkable())
+                    || scare_at(level, pos)
+                {
+                    continue;
+                }
+                spots += 1;
+                if rnd(&mut self.rng, spots) == 0 {
+                    spot = Some(pos);
+                }
+            }
+        }
+        let Some(pos) = spot else {
+            return false;
+        };
+        let depth = self.dungeon.depth;
+        let kind = rand_monster(depth, false, &mut self.rng);
+        let tp = new_monster(kind, pos, depth, &mut self.rng);
+        put_monster(&mut self.dungeon.level, tp);
+        true
+    }
+
+    /// Take the curse off whatever is under a pack letter (Rogue's
+    /// `uncurse`).
+    fn uncurse(&mut self, ch: Option<char>) {
+        if let Some(obj) = ch.and_then(|ch| self.player.pack.get_mut(ch)) {
+            obj.flags &= !IS_CURSED;
+        }
+    }
+
+    /// Read the scroll under letter `ch` (Rogue's `read_scroll`).
+    pub fn read_scroll(&mut self, ch: char) -> ReadOutcome {
+        let Some(kind) = self.player.pack.get(ch).map(|o| o.kind) else {
+            self.messages.push(String::from("You don't have that"));
+            return ReadOutcome::NotRead;
+        };
+        if kind != SCROLL {
+            let msg = if self.options.terse {
+                "Nothing to read"
+            } else {
+                "There is nothing on it to read"
+            };
+            self.messages.push(String::from(msg));
+            return ReadOutcome::NotRead;
+        }
+        let Some(obj) = self.player.pack.leave(ch, false) else {
+            return ReadOutcome::NotRead;
+        };
+        if self.player.cur_weapon == Some(ch) {
+            self.player.cur_weapon = None;
+        }
+
+        match obj.which {
+            S_CONFUSE => {
+                // The next monster the hero hits is confused.
+                self.player.creature.flags |= CAN_HUH;
+                let color = self.pick_color("red");
+                self.messages
+                    .push(format!("Your hands begin to glow {}", color));
+            }
+            S_ARMOR => {
+                let color = self.pick_color("silver");
+                if let Some(armor) = self
+                    .player
+                    .cur_armor
+                    .and_then(|c| self.player.pack.get_mut(c))
+                {
+                    armor.armor -= 1;
+                    armor.flags &= !IS_CURSED;
+                    self.messages
+                        .push(format!("Your armor glows {} for a moment", color));
+                }
+            }
+            S_HOLD => {
+                let held = self.hold_monsters();
+                if held > 0 {
+                    self.knowledge.identify(SCROLL, S_HOLD);
+                }
+                let msg = match held {
+                    0 => "You feel a strange sense of loss",
+                    1 => "The monster freezes",
+                    _ => "The monsters around you freeze",
+                };
+                self.messages.push(String::from(msg));
+            }
+            S_SLEEP => {
+                self.knowledge.identify(SCROLL, S_SLEEP);
+                self.player.no_command += rnd(&mut self.rng, SLEEPTIME) + 4;
+                self.player.creature.flags &= !IS_RUN;
+                self.messages.push(String::from("You fall asleep"));
+            }
+            S_CREATE => {
+                if !self.create_monster() {
+                    self.messages.push(String::from(
+                        "You hear a faint cry of anguish in the distance",
+                    ));
+                }
+            }
+            S_ID_POTION | S_ID_SCROLL | S_ID_WEAPON | S_ID_ARMOR | S_ID_R_OR_S => {
+                self.knowledge.identify(SCROLL, obj.which);
+                self.messages.push(format!(
+                    "This scroll is an {} scroll",
+                    SCR_INFO[obj.which as usize].name
+                ));
+                let kinds = id_kinds(obj.which);
+                if self
+                    .player
+                    .pack
+                    .items
+                    .iter()
+                    .any(|o| kinds.contains(&o.kind))
+                {
+                    return ReadOutcome::Identify(kinds);
+                }
+                self.messages
+                    .push(String::from("You don't have anything appropriate"));
+            }
+            S_MAP => {
+                self.knowledge.identify(SCROLL, S_MAP);
+                self.messages
+                    .push(String::from("Oh, now this scroll has a map on it"));
+                self.magic_map();
+            }
+            S_FDET => {
+                let positions: Vec<IVec2> = self
+                    .dungeon
+                    .level
+                    .objects
+                    .iter()
+                    .filter(|o| o.kind == FOOD)
+                    .map(|o| o.pos)
+                    .collect();
+                if positions.is_empty() {
+                    self.messages.push(String::from("Your nose tingles"));
+                } else {
+                    self.knowledge.identify(SCROLL, S_FDET);
+                    self.messages
+                        .push(String::from("Your nose tingles and you smell food."));
+                    self.detection = Some(Detection {
+                        class: TerrainCellClass::Food,
+                        positions,
+                    });
+                }
+            }
+            S_TELEP => {
+                let before = self.dungeon.level.room_at(self.player.pos());
+                self.teleport();
+                if self.dungeon.level.room_at(self.player.pos()) != before {
+                    self.knowledge.identify(SCROLL, S_TELEP);
+                }
+            }
+            S_ENCH => {
+                let weapon = self
+                    .player
+                    .cur_weapon
+                    .filter(|&c| self.player.pack.get(c).is_some_and(|o| o.kind == WEAPON));
+                match weapon {
+                    Some(c) => {
+                        let color = self.pick_color("blue");
+                        let plus_hit = rnd(&mut self.rng, 2) == 0;
+                        if let Some(weapon) = self.player.pack.get_mut(c) {
+                            weapon.flags &= !IS_CURSED;
+                            if plus_hit {
+                                weapon.hplus += 1;
+                            } else {
+                                weapon.dplus += 1;
+                            }
+                            let name = WEAP_INFO[weapon.which as usize].name;
+                            self.messages
+                                .push(format!("Your {} glows {} for a moment", name, color));
+                        }
+                    }
+                    None => self
+                        .messages
+                        .push(String::from("You feel a strange sense of loss")),
+                }
+            }
+            S_SCARE => {
+                // Reading it is a mistake and produces laughter at her
+                // poor boo boo.
+                self.messages
+                    .push(String::from("You hear maniacal laughter in the distance"));
+            }
+            S_REMOVE => {
+                self.uncurse(self.player.cur_armor);
+                self.uncurse(self.player.cur_weapon);
+                self.uncurse(self.player.cur_ring[LEFT]);
+                self.uncurse(self.player.cur_ring[RIGHT]);
+                let msg = self.choose_str(
+                    "You feel in touch with the Universal Onenes",
+                    "You feel as if somebody is watching over you",
+                );
+                self.messages.push(String::from(msg));
+            }
+            S_AGGR => {
+                self.aggravate();
+                self.messages
+                    .push(String::from("You hear a high pitched humming noise"));
+            }
+            S_PROTECT => {
+                let color = self.pick_color("gold");
+                match self
+                    .player
+                    .cur_armor
+                    .and_then(|c| self.player.pack.get_mut(c))
+                {
+                    Some(armor) => {
+                        armor.flags |= IS_PROT;
+                        self.messages.push(format!(
+                            "Your armor is covered by a shimmering {} shield",
+                            color
+                        ));
+                    }
+                    None => self
+                        .messages
+                        .push(String::from("You feel a strange sense of loss")),
+                }
+            }
+            _ => self.messages.push(String::from("What a puzzling scroll!")),
+        }
+        ReadOutcome::Read
+    }
+
+    /// Identify the object under letter `ch`, which must be one of `kinds`
+    /// (Rogue's `whatis`). Returns false, after saying why, if the player
+    /// has to pick again.
+    pub fn whatis(&mut self, ch: char, kinds: &[i32]) -> bool {
+        let Some(obj) = self.player.pack.get_mut(ch) else {
+            self.messages
+                .push(String::from("You must identify something"));
+            return false;
+        };
+        if !kinds.is_empty() && !kinds.contains(&obj.kind) {
+            let name = kinds_name(kinds);
+            self.messages
+                .push(format!("You must identify a{} {}", vowelstr(name), name));
+            return false;
+        }
+        obj.flags |= IS_KNOW;
+        let (kind, which) = (obj.kind, obj.which);
+        if matches!(kind, POTION | SCROLL | RING | STICK) {
+            self.knowledge.identify(kind, which);
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let name = self.inv_name(obj, false);
+            self.messages.push(name);
+        }
+        true
+    }
+}
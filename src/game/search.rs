@@ -0,0 +1,57 @@
+use glam::IVec2;
+
+use crate::dice::rnd;
+use crate::game::game_state::GameState;
+use crate::terrain::terrain_cell::{TerrainCellClass, F_PASS, F_SECRET};
+use crate::thing::{IS_BLIND, IS_HALU};
+
+impl GameState {
+    /// Look around the hero for secret doors and passages, which look like
+    /// wall and rock until found (Rogue's `search`). Hallucinating or blind heroes find less. Returns whether
+    /// anything turned up.
+    pub fn search(&mut self) -> bool {
+        let hero = self.player.creature.pos;
+        let mut probinc = 0;
+        if self.player.creature.on(IS_HALU) {
+            probinc += 3;
+        }
+        if self.player.creature.on(IS_BLIND) {
+            probinc += 2;
+        }
+
+        let mut found = false;
+        for y in hero.y - 1..=hero.y + 1 {
+            for x in hero.x - 1..=hero.x + 1 {
+                let pos = IVec2::new(x, y);
+                if pos == hero || x < 0 || y < 0 {
+                    continue;
+                }
+                let Some(cell) = self.dungeon.level.terrain.get_mut(x as usize, y as usize) else {
+                    continue;
+                };
+                if cell.flags & F_SECRET == 0 {
+                    continue;
+                }
+                let (found_class, odds) = match cell.cell_class {
+                    TerrainCellClass::WallHorizontal | TerrainCellClass::WallVertical => {
+                        (TerrainCellClass::Door, 5)
+                    }
+                    TerrainCellClass::Empty if cell.flags & F_PASS != 0 => {
+                        (TerrainCellClass::Passage, 3)
+                    }
+                    _ => continue,
+                };
+                if rnd(&mut self.rng, odds + probinc) != 0 {
+                    continue;
+                }
+                cell.cell_class = found_class;
+                cell.flags &= !F_SECRET;
+                if found_class == TerrainCellClass::Door {
+                    self.messages.push(String::from("A secret door"));
+                }
+                found = true;
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,384 @@
+use glam::IVec2;
+
+use crate::dice::{rnd, roll};
+use crate::game::game_state::GameState;
+use crate::game::weapons::do_motion;
+use crate::terrain::terrain_cell::F_SEEN;
+use crate::thing::chase::{relocate, runto, see_monst};
+use crate::thing::fight::{killed, save_throw, set_mname, VS_MAGIC};
+use crate::thing::knowledge::pick_color;
+use crate::thing::monster_table::new_monster;
+use crate::thing::object_table::{
+    FLAME, NO_LAUNCHER, WS_CANCEL, WS_COLD, WS_DRAIN, WS_ELECT, WS_FIRE, WS_HASTE_M, WS_INVIS,
+    WS_LIGHT, WS_MISSILE, WS_NOP, WS_POLYMORPH, WS_SLOW_M, WS_TELAWAY, WS_TELMON,
+};
+use crate::thing::{
+    ObjectThing, CAN_HUH, GOLD, IS_CANC, IS_HALU, IS_HASTE, IS_HELD, IS_INVIS, IS_MISL, IS_RUN,
+    IS_SLOW, STICK, WEAPON,
+};
+
+/* Squares a bolt from a stick travels, not counting bounces. */
+pub const BOLT_LENGTH: i32 = 6;
+
+impl GameState {
+    /// The monster a stick zapped in direction `dir` reaches: the first one
+    /// in a straight line before a wall.
+    fn zap_target(&self, dir: IVec2) -> Option<usize> {
+        if dir == IVec2::ZERO {
+            return None;
+        }
+        let level = &self.dungeon.level;
+        let mut pos = self.player.pos();
+        while level.cell_class_at(pos).is_some_and(|c| c.is_walkable())
+            && level.monster_at(pos).is_none()
+        {
+            pos += dir;
+        }
+        level.monsters.iter().position(|m| m.pos == pos)
+    }
+
+    /// Light up the room the hero is in, if there is one.
+    fn light_room(&mut self) {
+        let level = &mut self.dungeon.level;
+        let room = level
+            .room_at(self.player.pos())
+            .filter(|&r| !level.rooms[r].is_maze);
+        let Some(r) = room else {
+            self.messages
+                .push(String::from("The corridor glows and then fades"));
+            return;
+        };
+        level.rooms[r].is_dark = false;
+        let (pos, size) = (level.rooms[r].pos, level.rooms[r].size);
+        for y in pos.y..pos.y + size.y {
+            for x in pos.x..pos.x + size.x {
+                if let Some(cell) = level.terrain.get_mut(x as usize, y as usize) {
+                    cell.flags |= F_SEEN;
+                }
+            }
+        }
+        let mut msg = String::from("The room is lit");
+        if !self.options.terse {
+            let color = pick_color("blue", self.player.creature.on(IS_HALU), &mut self.rng);
+            msg += &format!(" by a shimmering {} light", color);
+        }
+        self.messages.push(msg);
+    }
+
+    /// Halve the hero's hit points and take them out of the monsters
+    /// close by: in the hero's room, or along the passage the hero is in
+    /// or at the door of (Rogue's `drain`).
+    fn drain(&mut self) {
+        let level = &self.dungeon.level;
+        let hero = self.player.pos();
+        let proom = level.room_at(hero);
+        let passages: Vec<usize> = (0..level.passages.len())
+            .filter(|&p| level.passages[p].tiles.contains(&hero))
+            .collect();
+        let drainee: Vec<IVec2> = level
+            .monsters
+            .iter()
+            .filter(|m| {
+                (proom.is_some() && level.room_at(m.pos) == proom)
+                    || passages
+                        .iter()
+                        .any(|&p| level.passages[p].tiles.contains(&m.pos))
+            })
+            .map(|m| m.pos)
+            .collect();
+        if drainee.is_empty() {
+            self.messages
+                .push(String::from("You have a tingling feeling"));
+            return;
+        }
+
+        let stats = &mut self.player.creature.stats;
+        stats.hp /= 2;
+        let cnt = stats.hp / drainee.len() as i32;
+        // Now zot all of the monsters.
+        for pos in drainee {
+            let Some(idx) = self
+                .dungeon
+                .level
+                .monsters
+                .iter()
+                .position(|m| m.pos == pos)
+            else {
+                continue;
+            };
+            let level = &mut self.dungeon.level;
+            level.monsters[idx].stats.hp -= cnt;
+            if level.monsters[idx].stats.hp <= 0 {
+                let pr = see_monst(level, &self.player.creature, &level.monsters[idx]);
+                killed(
+                    &mut self.dungeon,
+                    &mut self.player.creature,
+                    idx,
+                    pr,
+                    &mut self.messages,
+                    &mut self.rng,
+                );
+                if let Some(lvl) = self.player.check_level(&mut self.rng) {
+                    self.messages.push(format!("Welcome to level {}", lvl));
+                }
+            } else {
+                runto(level, pos, &self.player.creature, &mut self.rng);
+            }
+        }
+    }
+
+    /// Fire a bolt of lightning, fire or ice from `start`, which bounces
+    /// off walls and may come back at whoever fired it (Rogue's
+    /// `fire_bolt`). `name` is what the bolt is called in messages.
+    pub fn fire_bolt(&mut self, start: IVec2, dir: IVec2, name: &str) {
+        if dir == IVec2::ZERO {
+            return;
+        }
+        let mut bolt = ObjectThing::new(WEAPON, FLAME);
+        bolt.hurl_dmg = String::from("6x6");
+        bolt.hplus = 100;
+        bolt.label = Some(name.to_string());
+
+        let from_hero = start == self.player.pos();
+        let mut dir = dir;
+        let mut pos = start;
+        let mut hit_hero = !from_hero;
+        let mut used = false;
+        let mut changed = false;
+        let mut length = 0;
+        while length < BOLT_LENGTH && !used {
+            pos += dir;
+            let level = &self.dungeon.level;
+            // Bolts reflect off walls and blank space but fly through doors.
+            let bounces = level.monster_at(pos).is_none()
+                && level
+                    .cell_class_at(pos)
+                    .is_none_or(|class| !class.is_walkable());
+            if bounces {
+                if !changed {
+                    hit_hero = !hit_hero;
+                }
+                changed = false;
+                dir = -dir;
+                self.messages.push(format!("The {} bounces", name));
+                continue;
+            }
+            length += 1;
+
+            let level = &self.dungeon.level;
+            let tp = level.monsters.iter().position(|m| m.pos == pos);
+            if let (false, Some(idx)) = (hit_hero, tp) {
+                hit_hero = true;
+                changed = !changed;
+                let tp = &level.monsters[idx];
+                if !save_throw(VS_MAGIC, &tp.stats, &mut self.rng) {
+                    used = true;
+                    if tp.kind == 'D' && name == "flame" {
+                        let msg = if self.options.terse {
+                            "The flame bounces"
+                        } else {
+                            "The flame bounces off the dragon"
+                        };
+                        self.messages.push(String::from(msg));
+                    } else {
+                        self.hit_monster(pos, &bolt);
+                    }
+                } else if tp.kind != 'X' || tp.disguise == 'X' {
+                    let mname = set_mname(level, &self.player.creature, tp);
+                    if from_hero {
+                        runto(
+                            &mut self.dungeon.level,
+                            pos,
+                            &self.player.creature,
+                            &mut self.rng,
+                        );
+                    }
+                    let msg = if self.options.terse {
+                        format!("The {} misses", name)
+                    } else {
+                        format!("The {} whizzes past {}", name, mname)
+                    };
+                    self.messages.push(msg);
+                }
+            } else if hit_hero && pos == self.player.pos() {
+                hit_hero = false;
+                changed = !changed;
+                if !self.player.save(VS_MAGIC, &mut self.rng) {
+                    used = true;
+                    self.player.creature.stats.hp -= roll(&mut self.rng, 6, 6);
+                    let msg = if self.options.terse {
+                        format!("The {} hits", name)
+                    } else {
+                        format!("You are hit by the {}", name)
+                    };
+                    self.messages.push(msg);
+                    if self.player.is_dead() {
+                        let killer = if from_hero {
+                            'b'
+                        } else {
+                            self.dungeon.level.monster_at(start).map_or('b', |m| m.kind)
+                        };
+                        self.killer = Some(killer);
+                    }
+                } else {
+                    self.messages.push(format!("The {} whizzes by you", name));
+                }
+            }
+        }
+    }
+
+    /// Zap the stick under letter `ch` in direction `dir` (Rogue's
+    /// `do_zap`). Returns false if no time passed.
+    pub fn zap(&mut self, ch: char, dir: IVec2) -> bool {
+        let Some(obj) = self.player.pack.get(ch) else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        if obj.kind != STICK {
+            self.messages.push(String::from("You can't zap with that!"));
+            return false;
+        }
+        if obj.armor == 0 {
+            self.messages.push(String::from("Nothing happens"));
+            return true;
+        }
+        let which = obj.which;
+
+        match which {
+            WS_LIGHT => {
+                self.knowledge.identify(STICK, WS_LIGHT);
+                self.light_room();
+            }
+            WS_DRAIN => {
+                if self.player.stats().hp < 2 {
+                    self.messages
+                        .push(String::from("You are too weak to use it"));
+                    return true;
+                }
+                self.drain();
+            }
+            WS_INVIS | WS_POLYMORPH | WS_TELAWAY | WS_TELMON | WS_CANCEL => {
+                if let Some(idx) = self.zap_target(dir) {
+                    self.zap_monster(which, idx, dir);
+                }
+            }
+            WS_MISSILE => {
+                self.knowledge.identify(STICK, WS_MISSILE);
+                let mut bolt = ObjectThing::new(GOLD, 0);
+                bolt.hurl_dmg = String::from("1x4");
+                bolt.hplus = 100;
+                bolt.dplus = 1;
+                bolt.flags = IS_MISL;
+                bolt.launch = self.player.weapon().map_or(NO_LAUNCHER, |w| w.which);
+                let pos = do_motion(&self.dungeon.level, self.player.pos(), dir);
+                let saved = match self.dungeon.level.monster_at(pos) {
+                    Some(tp) => save_throw(VS_MAGIC, &tp.stats, &mut self.rng),
+                    None => true,
+                };
+                if !saved {
+                    self.hit_monster(pos, &bolt);
+                } else {
+                    let msg = if self.options.terse {
+                        "Missile vanishes"
+                    } else {
+                        "The missile vanishes with a puff of smoke"
+                    };
+                    self.messages.push(String::from(msg));
+                }
+            }
+            WS_HASTE_M | WS_SLOW_M => {
+                if let Some(idx) = self.zap_target(dir) {
+                    let tp = &mut self.dungeon.level.monsters[idx];
+                    if which == WS_HASTE_M {
+                        if tp.on(IS_SLOW) {
+                            tp.flags &= !IS_SLOW;
+                        } else {
+                            tp.flags |= IS_HASTE;
+                        }
+                    } else {
+                        if tp.on(IS_HASTE) {
+                            tp.flags &= !IS_HASTE;
+                        } else {
+                            tp.flags |= IS_SLOW;
+                        }
+                        tp.turn = true;
+                    }
+                    let pos = tp.pos;
+                    runto(
+                        &mut self.dungeon.level,
+                        pos,
+                        &self.player.creature,
+                        &mut self.rng,
+                    );
+                }
+            }
+            WS_ELECT | WS_FIRE | WS_COLD => {
+                let name = match which {
+                    WS_ELECT => "bolt",
+                    WS_FIRE => "flame",
+                    _ => "ice",
+                };
+                self.fire_bolt(self.player.pos(), dir, name);
+                self.knowledge.identify(STICK, which);
+            }
+            WS_NOP => {}
+            _ => self.messages.push(String::from("What a bizarre schtick!")),
+        }
+
+        if let Some(obj) = self.player.pack.get_mut(ch) {
+            obj.armor -= 1;
+        }
+        true
+    }
+
+    /// Do what a stick of invisibility, polymorph, teleport away, teleport
+    /// to or cancellation does to the monster at index `idx`.
+    fn zap_monster(&mut self, which: i32, idx: usize, dir: IVec2) {
+        let level = &mut self.dungeon.level;
+        if level.monsters[idx].kind == 'F' {
+            self.player.creature.flags &= !IS_HELD;
+        }
+        match which {
+            WS_INVIS => level.monsters[idx].flags |= IS_INVIS,
+            WS_POLYMORPH => {
+                let old = &level.monsters[idx];
+                let kind = (b'A' + rnd(&mut self.rng, 26) as u8) as char;
+                let mut tp = new_monster(kind, old.pos, self.dungeon.depth, &mut self.rng);
+                tp.old_ch = old.old_ch;
+                tp.room_id = old.room_id;
+                tp.pack = std::mem::take(&mut level.monsters[idx].pack);
+                level.monsters[idx] = tp;
+                if see_monst(level, &self.player.creature, &level.monsters[idx]) {
+                    self.knowledge.identify(STICK, WS_POLYMORPH);
+                }
+            }
+            WS_CANCEL => {
+                let tp = &mut level.monsters[idx];
+                tp.flags |= IS_CANC;
+                tp.flags &= !(IS_INVIS | CAN_HUH);
+                tp.disguise = tp.kind;
+            }
+            WS_TELAWAY | WS_TELMON => {
+                let hero = self.player.pos();
+                let new_pos = if which == WS_TELAWAY {
+                    loop {
+                        match level.find_floor(None, 0, true, &mut self.rng) {
+                            Some(pos) if pos != hero => break Some(pos),
+                            Some(_) => {}
+                            None => break None,
+                        }
+                    }
+                } else {
+                    Some(hero + dir)
+                };
+                let tp = &mut level.monsters[idx];
+                tp.dest = None;
+                tp.flags |= IS_RUN;
+                if let Some(new_pos) = new_pos {
+                    relocate(level, idx, new_pos);
+                }
+            }
+            _ => {}
+        }
+    }
+}
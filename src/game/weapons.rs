@@ -0,0 +1,161 @@
+use glam::IVec2;
+
+use crate::dice::rnd;
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::game::game_state::GameState;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::fight::{fall, fight, FightOutcome, RingBonus, Weapon};
+use crate::thing::object_table::WEAP_INFO;
+use crate::thing::{ObjectThing, ARMOR, IS_MANY, IS_MISL, WEAPON};
+
+/// Where something flying from `start` in direction `dir` comes down: on
+/// the first monster, wall or door in its way (Rogue's `do_motion`).
+pub fn do_motion(level: &DungeonLevel, start: IVec2, dir: IVec2) -> IVec2 {
+    if dir == IVec2::ZERO {
+        return start;
+    }
+    let mut pos = start;
+    loop {
+        pos += dir;
+        match level.cell_class_at(pos) {
+            Some(class)
+                if class.is_walkable()
+                    && class != TerrainCellClass::Door
+                    && level.monster_at(pos).is_none() => {}
+            _ => return pos,
+        }
+    }
+}
+
+impl GameState {
+    /// Take the object under `ch` in hand, putting down the weapon held
+    /// before (Rogue's `wield`). Returns false if no time passed.
+    pub fn wield(&mut self, ch: char) -> bool {
+        let Some(kind) = self.player.pack.get(ch).map(|o| o.kind) else {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        };
+        let old_weapon = self.player.cur_weapon;
+        if let Some(old) = old_weapon {
+            if !self.dropcheck(old) {
+                self.player.cur_weapon = old_weapon;
+                return true;
+            }
+        }
+        self.player.cur_weapon = old_weapon;
+        if kind == ARMOR {
+            self.messages.push(String::from("You can't wield armor"));
+            return false;
+        }
+        if self.is_current(ch) {
+            return false;
+        }
+        if let Some(obj) = self.player.pack.get(ch) {
+            let name = self.inv_name(obj, true);
+            let msg = if self.options.terse {
+                format!("Wielding {} ({})", name, ch)
+            } else {
+                format!("You are now wielding {} ({})", name, ch)
+            };
+            self.messages.push(msg);
+        }
+        self.player.cur_weapon = Some(ch);
+        true
+    }
+
+    /// Throw the object under `ch` in direction `dir` (Rogue's `missile`).
+    /// One comes off a stack; it flies until it meets a monster, wall or
+    /// door. A missile that hits is used up, and one that misses lands
+    /// nearby, or breaks if there is no room. Arrows, darts and shuriken
+    /// that miss break one time in ten. Returns false if no time passed.
+    pub fn throw(&mut self, ch: char, dir: IVec2) -> bool {
+        if self.player.pack.get(ch).is_none() {
+            self.messages.push(String::from("You don't have that"));
+            return false;
+        }
+        if !self.dropcheck(ch) || self.is_current(ch) {
+            return false;
+        }
+        let Some(mut obj) = self.player.pack.leave(ch, false) else {
+            return false;
+        };
+        obj.pos = do_motion(&self.dungeon.level, self.player.pos(), dir);
+        let hit = matches!(
+            self.hit_monster(obj.pos, &obj),
+            Some(FightOutcome::Hit | FightOutcome::Killed)
+        );
+        if !hit {
+            let name = if obj.kind == WEAPON {
+                format!("The {}", WEAP_INFO[obj.which as usize].name)
+            } else {
+                String::from("It")
+            };
+            let hero_pos = self.player.pos();
+            let fragile = obj.on(IS_MANY) && obj.on(IS_MISL);
+            if fragile && rnd(&mut self.rng, 10) == 0 {
+                self.messages.push(format!("{} breaks", name));
+            } else if !fall(&mut self.dungeon.level, obj, hero_pos, &mut self.rng) {
+                self.messages
+                    .push(format!("{} vanishes as it hits the ground", name));
+            }
+        }
+        true
+    }
+
+    /// Attack the monster at `pos` with whatever is in hand (Rogue's
+    /// `fight` when the hero moves into a monster). Returns `None` if no
+    /// monster is there.
+    pub fn melee(&mut self, pos: IVec2) -> Option<FightOutcome> {
+        self.hero_fight(pos, None)
+    }
+
+    /// Hit the monster at `pos` with something thrown or fired (Rogue's
+    /// `hit_monster`). Returns `None` if no monster is there.
+    pub fn hit_monster(&mut self, pos: IVec2, missile: &ObjectThing) -> Option<FightOutcome> {
+        self.hero_fight(pos, Some(missile))
+    }
+
+    /// Have the hero fight the monster at `pos`, in hand or with a missile.
+    fn hero_fight(&mut self, pos: IVec2, missile: Option<&ObjectThing>) -> Option<FightOutcome> {
+        let idx = self
+            .dungeon
+            .level
+            .monsters
+            .iter()
+            .position(|m| m.pos == pos)?;
+        self.player.quiet = 0;
+        let wielded = self.player.cur_weapon.and_then(|c| self.player.pack.get(c));
+        let weapon = match missile {
+            Some(missile) => Weapon::Thrown { missile, wielded },
+            None => wielded.map_or(Weapon::Hands, Weapon::Wielded),
+        };
+        // Rings of increase damage and dexterity only help the weapon in hand.
+        let in_hand = match weapon {
+            Weapon::Hands => false,
+            Weapon::Wielded(_) => true,
+            Weapon::Thrown { missile, wielded } => {
+                wielded.is_some_and(|w| std::ptr::eq(w, missile))
+            }
+        };
+        let rings = if in_hand {
+            self.player.ring_bonus()
+        } else {
+            RingBonus::default()
+        };
+        let outcome = fight(
+            &mut self.dungeon,
+            &mut self.player.creature,
+            idx,
+            weapon,
+            rings,
+            &mut self.messages,
+            &mut self.rng,
+        );
+        if outcome == FightOutcome::Killed {
+            if let Some(level) = self.player.check_level(&mut self.rng) {
+                self.messages.push(format!("Welcome to level {}", level));
+            }
+        }
+        Some(outcome)
+    }
+}
@@ -3,3 +3,7 @@ pub mod terrain;
 pub mod thing;
 pub mod terrain_structure;
 pub mod dungeon_level;
+pub mod dice;
+pub mod rng;
+pub mod game;
+pub mod export;
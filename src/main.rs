@@ -1,144 +1,287 @@
 extern crate ncurses;
 
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use glam::IVec2;
 use ncurses::*;
-use xegue::dungeon_level::dungeon_level::generate_dungeon_level;
-use xegue::terminal::draw_terrain::terrain_to_lines;
+use rand::Rng;
+use xegue::game::command::{Command, CommandReader, Input, Key, KeyMap};
+use xegue::game::commands::Outcome;
+use xegue::game::game_state::GameState;
+use xegue::game::message::MessageLine;
+use xegue::game::save::{restore_game, save_game, RestoreOptions};
+use xegue::terminal::status_line::{Status, StatusLine, StatusStyle};
 use xegue::terminal::terminal_symbol::TerminalSymbol;
+use xegue::terrain::terrain_cell::{TerrainCellClass, F_SEEN};
+use xegue::thing::chase::see_monst;
+use xegue::thing::fight::killname;
+use xegue::thing::{IS_HALU, SEE_MONST};
 
-fn manhattan(a: IVec2, b: IVec2) -> i32 {
-    (a.x - b.x).abs() + (a.y - b.y).abs()
-}
+/* Where a game is saved when no save file was named (Rogue's `rogue.save`). */
+const DEFAULT_SAVE: &str = "xegue.save";
 
-fn distance_squared(a: IVec2, b: IVec2) -> i32 {
-    let dx = a.x - b.x;
-    let dy = a.y - b.y;
-    dx * dx + dy * dy
+/// Turn what `getch` returns into a key the game knows, if it is one.
+fn to_key(ch: i32) -> Option<Key> {
+    match ch {
+        KEY_UP => Some(Key::Up),
+        KEY_DOWN => Some(Key::Down),
+        KEY_LEFT => Some(Key::Left),
+        KEY_RIGHT => Some(Key::Right),
+        KEY_A1 | KEY_HOME => Some(Key::UpLeft),
+        KEY_A3 | KEY_PPAGE => Some(Key::UpRight),
+        KEY_C1 | KEY_END => Some(Key::DownLeft),
+        KEY_C3 | KEY_NPAGE => Some(Key::DownRight),
+        KEY_B2 => Some(Key::Center),
+        0..=0x7f => Some(Key::Char(ch as u8 as char)),
+        _ => None,
+    }
 }
 
-fn main() {
-    // Init ncurses
-    initscr(); // Initialize the window
-    raw(); // Disable line buffering
-    keypad(stdscr(), true); // Enable function keys
-    noecho(); // Disable echoing of input characters
-
-    // Test Coord struct
-    let player_pos = IVec2::new(10, 5);
-    let treasure_pos = IVec2::new(15, 8);
-
-    // Calculate distances
-    let manhattan = manhattan(player_pos, treasure_pos);
-    let euclidean_sq = distance_squared(player_pos, treasure_pos);
-
-    // Print text
-    let _ = addstr("=== xegue Coordinate System Test ===\n\n");
-    let _ = addstr(&format!(
-        "Player position: ({}, {})\n",
-        player_pos.x, player_pos.y
-    ));
-    let _ = addstr(&format!(
-        "Treasure position: ({}, {})\n\n",
-        treasure_pos.x, treasure_pos.y
-    ));
-    let _ = addstr(&format!("Manhattan distance: {}\n", manhattan));
-    let _ = addstr(&format!("Euclidean distance²: {}\n\n", euclidean_sq));
-
-    // Test coordinate arithmetic
-    let delta = IVec2::new(2, -1);
-    let new_pos = player_pos + delta;
-    let _ = addstr(&format!(
-        "Move by ({}, {}): new position ({}, {})\n\n",
-        delta.x, delta.y, new_pos.x, new_pos.y
-    ));
-
-    // Test ncurses coordinate conversion
-    let (y, x) = (player_pos.y, player_pos.x);
-    let _ = addstr(&format!("For ncurses mvaddch: y={}, x={}\n\n", y, x));
-
-    // DungeonLevel generation test
-    let level = generate_dungeon_level();
-    // Check if the rooms list is empty or not
-    if level.rooms.is_empty() {
-        let _ = addstr("No rooms generated!\n");
-    } else {
-        let _ = addstr(&format!("Generated {} rooms.\n", level.rooms.len()));
-        // Print details of the first few rooms
-        for (i, room) in level.rooms.iter().take(3).enumerate() {
-            let _ = addstr(&format!(
-                "Room {}: pos=({}, {}), size=({}, {}), is_maze={}, is_gone={}, is_dark={}\n",
-                i,
-                room.pos.x,
-                room.pos.y,
-                room.size.x,
-                room.size.y,
-                room.is_maze,
-                room.is_gone,
-                room.is_dark
-            ));
+/// Wait for a space, or escape (Rogue's `wait_for`).
+fn wait_for_space() -> bool {
+    loop {
+        match getch() {
+            0x20 => return true,
+            0x1b => return false,
+            _ => {}
         }
     }
+}
 
-    // Test / display passages
-    // if level.passages.is_empty() {
-    //     let _ = addstr("No passages carved.\n");
-    // } else {
-    //     let _ = addstr(&format!(
-    //         "Generated {} passages.\n",
-    //         level.passages.len()
-    //     ));
-    //     for (i, p) in level.passages.iter().take(5).enumerate() {
-    //         let _ = addstr(&format!(
-    //             "Passage {}: rooms {}-{}, door_a=({}, {}), door_b=({}, {}), tiles={}\n",
-    //             i,
-    //             p.room_a,
-    //             p.room_b,
-    //             p.door_a.x,
-    //             p.door_a.y,
-    //             p.door_b.x,
-    //             p.door_b.y,
-    //             p.tiles.len()
-    //         ));
-    //     }
-    // }
-
-    let _ = addstr("Dungeon level generation test:\n");
-    for line in terrain_to_lines(&level.terrain) {
-        let _ = addstr(&line);
-        let _ = addstr("\n");
-    }
-    let _ = addstr("\n");
+/* What a hallucinating hero takes the objects lying about for. */
+const TRIP_OBJECTS: [TerminalSymbol; 10] = [
+    TerminalSymbol::Gold,
+    TerminalSymbol::Potion,
+    TerminalSymbol::Scroll,
+    TerminalSymbol::Food,
+    TerminalSymbol::Weapon,
+    TerminalSymbol::Armor,
+    TerminalSymbol::Amulet,
+    TerminalSymbol::Ring,
+    TerminalSymbol::Stick,
+    TerminalSymbol::Magic,
+];
 
-    // Display marker at player position
+/// Whether a map cell shows an object lying there.
+fn is_object(class: TerrainCellClass) -> bool {
+    use TerrainCellClass::*;
+    matches!(
+        class,
+        Gold | Potion | Scroll | Magic | Food | Weapon | Armor | Amulet | Ring | Stick
+    )
+}
+
+/// Draw what the hero knows of the level, the monsters the hero can see
+/// and the hero (Rogue's `look`). Map rows are screen rows; the message
+/// line and the status line cover the top and bottom ones. Only cells
+/// seen before are drawn, without the floor of a dark room away from the
+/// hero, and a hallucinating hero sees things as something else.
+fn draw_map(game: &GameState) {
+    let level = &game.dungeon.level;
+    let hero = &game.player.creature;
+    let trip = hero.on(IS_HALU);
+    let mut rng = rand::rng();
+    let rows = (LINES() - 1).min(level.terrain.height as i32);
+    let cols = COLS().min(level.terrain.width as i32);
+    for y in 1..rows {
+        for x in 0..cols {
+            let pos = IVec2::new(x, y);
+            let Some(cell) = level.terrain.get(x as usize, y as usize) else {
+                continue;
+            };
+            let forgotten = cell.cell_class == TerrainCellClass::Floor
+                && level.is_dark_at(pos)
+                && (pos - hero.pos).abs().max_element() > 1;
+            let ch = if cell.flags & F_SEEN == 0 || forgotten {
+                ' '
+            } else if trip && is_object(cell.cell_class) {
+                TRIP_OBJECTS[rng.random_range(0..TRIP_OBJECTS.len())].as_char()
+            } else {
+                TerminalSymbol::char_from_terrain_cell_class(cell.cell_class)
+            };
+            let _ = mvaddch(y, x, ch as u32);
+        }
+    }
+    if let Some(detection) = &game.detection {
+        let ch = TerminalSymbol::char_from_terrain_cell_class(detection.class);
+        for pos in &detection.positions {
+            let _ = mvaddch(pos.y, pos.x, ch as u32);
+        }
+    }
+    for tp in &level.monsters {
+        if see_monst(level, hero, tp) || hero.on(SEE_MONST) {
+            let ch = if trip {
+                rng.random_range('A'..='Z')
+            } else {
+                tp.kind
+            };
+            let _ = mvaddch(tp.pos.y, tp.pos.x, ch as u32);
+        }
+    }
     let _ = mvaddch(
-        player_pos.y,
-        player_pos.x,
+        hero.pos.y,
+        hero.pos.x,
         TerminalSymbol::Player.as_char() as u32,
     );
-    let _ = mvaddstr(player_pos.y + 1, player_pos.x - 1, "YOU");
+}
 
-    // Display marker at treasure position
-    let _ = mvaddch(
-        treasure_pos.y,
-        treasure_pos.x,
-        TerminalSymbol::Gold.as_char() as u32,
-    );
-    let _ = mvaddstr(treasure_pos.y + 1, treasure_pos.x - 1, "GOLD");
+/// Draw the top line, waiting out each `--More--` (Rogue's `endmsg`).
+fn draw_messages(line: &mut MessageLine) {
+    loop {
+        let _ = mvaddstr(0, 0, &line.display());
+        clrtoeol();
+        if !line.is_waiting() {
+            return;
+        }
+        refresh();
+        if wait_for_space() {
+            line.acknowledge();
+        } else {
+            line.skip();
+        }
+    }
+}
 
-    // Eq test
-    let _ = addstr(&format!(
-        "(2,3) is  equal to (2,3): {}\n",
-        IVec2::new(2, 3) == IVec2::new(2, 3)
-    ));
+/// Show lines over the map until a key is pressed.
+fn show_window(lines: &[String]) {
+    clear();
+    for (y, text) in lines.iter().enumerate() {
+        let _ = mvaddstr(y as i32, 0, text);
+    }
+    let _ = mvaddstr(LINES() - 1, 0, "--Press space to continue--");
+    refresh();
+    wait_for_space();
+    clear();
+}
 
-    let _ = mvaddstr(LINES() - 1, 0, "Press any key to exit...");
+fn main() {
+    let numpad = std::env::args().any(|arg| arg == "--numpad");
+    // A save file named on the command line is restored (Rogue's
+    // `rogue file`), and the game is saved back to it.
+    let restore = std::env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let save_path = PathBuf::from(restore.as_deref().unwrap_or(DEFAULT_SAVE));
+    let mut game = match restore {
+        Some(_) => {
+            let options = RestoreOptions { delete_save: true };
+            match restore_game(&save_path, options) {
+                Ok(game) => game,
+                Err(err) => {
+                    eprintln!("{}: {}", save_path.display(), err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => {
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as u32);
+            GameState::new(seed)
+        }
+    };
 
-    // Refresh the screen
-    refresh();
+    initscr();
+    raw();
+    keypad(stdscr(), true);
+    noecho();
 
-    // Wait for user input
-    getch();
+    let mut line = MessageLine::new(COLS() as usize);
+    let mut status = StatusLine::default();
+    let keymap = if numpad {
+        KeyMap::numpad()
+    } else {
+        KeyMap::vi_keys()
+    };
+    let mut reader = CommandReader::new(keymap);
+    line.msg("Hello, welcome to the Dungeons of Doom");
+
+    let mut quit = false;
+    let mut saved = false;
+    while game.killer.is_none() && !quit {
+        line.terse = game.options.terse;
+        reader.terse = game.options.terse;
+        line.post(&mut game.messages);
+        draw_map(&game);
+        draw_messages(&mut line);
+        let now = Status::new(&game.player, game.dungeon.depth);
+        if let Some(spans) = status.update(now, COLS() as usize) {
+            mv(LINES() - 1, 0);
+            for (text, style) in spans {
+                if style == StatusStyle::Alert {
+                    attron(A_STANDOUT);
+                }
+                let _ = addstr(&text);
+                attroff(A_STANDOUT);
+            }
+            clrtoeol();
+        }
+        let pos = game.player.pos();
+        mv(pos.y, pos.x);
+        refresh();
 
-    // End ncurses mode
+        let Some(key) = to_key(getch()) else {
+            continue;
+        };
+        let input = reader.feed(key);
+        if !matches!(input, Input::Ready(Command::PrevMessage, _)) {
+            line.clear();
+        }
+        match input {
+            Input::Pending(Some(prompt)) => line.msg(&prompt),
+            Input::Pending(None) | Input::Cancelled => {}
+            Input::Unknown(key) => {
+                if !game.options.terse {
+                    let name = match key {
+                        Key::Char(ch) if ch.is_ascii_control() => {
+                            format!("^{}", (ch as u8 + b'@') as char)
+                        }
+                        Key::Char(ch) => ch.to_string(),
+                        _ => format!("{:?}", key),
+                    };
+                    line.msg(&format!("Illegal command '{}'", name));
+                }
+            }
+            Input::Ready(Command::PrevMessage, _) => line.previous(),
+            Input::Ready(Command::Quit, _) => {
+                line.msg("Really quit?");
+                draw_messages(&mut line);
+                refresh();
+                quit = getch() == 'y' as i32;
+                line.clear();
+            }
+            Input::Ready(Command::Save, _) => match save_game(&game, &save_path) {
+                Ok(()) => {
+                    saved = true;
+                    quit = true;
+                }
+                Err(err) => line.msg(&format!("Could not save the game: {}", err)),
+            },
+            Input::Ready(command, count) => match game.perform(&command, count) {
+                Outcome::Identify(kinds) => {
+                    line.post(&mut game.messages);
+                    if let Input::Pending(Some(prompt)) = reader.ask_identify(kinds) {
+                        line.msg(&prompt);
+                    }
+                }
+                Outcome::Show(lines) if !lines.is_empty() => {
+                    show_window(&lines);
+                    status.invalidate();
+                }
+                _ => {}
+            },
+        }
+    }
+
+    if let Some(killer) = game.killer {
+        line.post(&mut game.messages);
+        line.msg(&format!("Killed by {}", killname(killer, true)));
+        draw_map(&game);
+        draw_messages(&mut line);
+        refresh();
+        wait_for_space();
+    }
     endwin();
+    if saved {
+        println!("Game saved to {}", save_path.display());
+    }
 }
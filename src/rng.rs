@@ -0,0 +1,49 @@
+use rand::RngCore;
+
+/// The game's random number generator: Rogue's linear congruential
+/// generator (`RN` in `rogue.h`), kept so that a game is reproducible from
+/// its seed and its whole state fits in one `u32` for saving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRng {
+    seed: u32,
+}
+
+impl GameRng {
+    pub fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    /// Seed from the operating system, for games started without a seed.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Current internal state; `GameRng::new(state)` resumes the sequence.
+    pub fn state(&self) -> u32 {
+        self.seed
+    }
+
+    /// Next 16 random bits.
+    fn next_u16(&mut self) -> u32 {
+        self.seed = self.seed.wrapping_mul(11109).wrapping_add(13849);
+        (self.seed >> 16) & 0xffff
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u16() << 16) | self.next_u16()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod terminal_symbol;
-pub mod draw_terrain;
\ No newline at end of file
+pub mod draw_terrain;
+pub mod parse_terrain;
+pub mod status_line;
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::terminal::terminal_symbol::TerminalSymbol;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::terrain::terrain_grid::TerrainGrid;
+
+/// What a character in a text map stands for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LegendEntry {
+    /// A plain terrain cell, as drawn by `terrain_to_lines`.
+    Terrain(TerrainCellClass),
+    /// The player's starting spot; the floor under it is inferred.
+    Player,
+    /// A monster of the given kind (`'A'..='Z'`); the floor under it is inferred.
+    Monster(char),
+    /// A door that still looks like wall until it is found.
+    HiddenDoor,
+    /// A stretch of passage that still looks like rock until it is found.
+    HiddenPassage,
+    /// Floor in a room that should be marked dark.
+    DarkRoom,
+    /// Floor in a room that should be marked as a treasure room.
+    TreasureRoom,
+}
+
+/// Mapping from map characters to what they stand for.
+///
+/// The default legend understands every `TerminalSymbol` character plus
+/// `A`-`Z` for monsters, `&` for a hidden door, `~` for a hidden passage,
+/// `d` for dark-room floor and `t` for treasure-room floor.
+#[derive(Clone, Debug)]
+pub struct MapLegend {
+    pub entries: HashMap<char, LegendEntry>,
+}
+
+impl Default for MapLegend {
+    fn default() -> Self {
+        let mut entries = HashMap::new();
+        for ch in (0u8..128).map(char::from) {
+            if let Some(symbol) = TerminalSymbol::from_char(ch) {
+                entries.insert(ch, LegendEntry::Terrain(symbol.to_terrain_cell_class()));
+            }
+        }
+        entries.insert(TerminalSymbol::Player.as_char(), LegendEntry::Player);
+        for kind in 'A'..='Z' {
+            entries.insert(kind, LegendEntry::Monster(kind));
+        }
+        entries.insert('&', LegendEntry::HiddenDoor);
+        entries.insert('~', LegendEntry::HiddenPassage);
+        entries.insert('d', LegendEntry::DarkRoom);
+        entries.insert('t', LegendEntry::TreasureRoom);
+        Self { entries }
+    }
+}
+
+impl MapLegend {
+    /// Add or replace the meaning of a character.
+    pub fn with(mut self, ch: char, entry: LegendEntry) -> Self {
+        self.entries.insert(ch, entry);
+        self
+    }
+
+    pub fn get(&self, ch: char) -> Option<LegendEntry> {
+        self.entries.get(&ch).copied()
+    }
+}
+
+/// Errors from reading a text map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MapParseError {
+    /// The map has no lines or only empty lines.
+    Empty,
+    /// A character that neither the symbols nor the legend know about.
+    UnknownSymbol { ch: char, x: usize, y: usize },
+    /// A legend entry that only makes sense for a full level, such as a
+    /// monster, was found while reading plain terrain.
+    NotTerrain { ch: char, x: usize, y: usize },
+    /// More than one player marker was found.
+    MultiplePlayers { x: usize, y: usize },
+    /// A legend entry names a monster kind outside `'A'..='Z'`.
+    UnknownMonster { kind: char, x: usize, y: usize },
+}
+
+impl fmt::Display for MapParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapParseError::Empty => write!(f, "map is empty"),
+            MapParseError::UnknownSymbol { ch, x, y } => {
+                write!(f, "unknown map symbol {:?} at ({}, {})", ch, x, y)
+            }
+            MapParseError::NotTerrain { ch, x, y } => {
+                write!(
+                    f,
+                    "map symbol {:?} at ({}, {}) is not plain terrain",
+                    ch, x, y
+                )
+            }
+            MapParseError::MultiplePlayers { x, y } => {
+                write!(f, "second player marker at ({}, {})", x, y)
+            }
+            MapParseError::UnknownMonster { kind, x, y } => {
+                write!(f, "unknown monster kind {:?} at ({}, {})", kind, x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapParseError {}
+
+/// Read a text map into a grid of legend entries, padding short lines
+/// with empty cells. Returns the entries in row-major order with the width
+/// and height of the map.
+pub fn lines_to_legend_entries<I, S>(
+    lines: I,
+    legend: &MapLegend,
+) -> Result<(Vec<LegendEntry>, usize, usize), MapParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let rows: Vec<Vec<char>> = lines
+        .into_iter()
+        .map(|line| line.as_ref().chars().collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let height = rows.len();
+    if width == 0 {
+        return Err(MapParseError::Empty);
+    }
+
+    let empty = LegendEntry::Terrain(TerrainCellClass::Empty);
+    let mut entries = vec![empty; width * height];
+    for (y, row) in rows.iter().enumerate() {
+        for (x, &ch) in row.iter().enumerate() {
+            entries[y * width + x] =
+                legend
+                    .get(ch)
+                    .ok_or(MapParseError::UnknownSymbol { ch, x, y })?;
+        }
+    }
+
+    Ok((entries, width, height))
+}
+
+/// Convert lines of text back into a `TerrainGrid`; the inverse of
+/// `terrain_to_lines`.
+///
+/// Only `TerminalSymbol` characters are accepted; use
+/// `parse_dungeon_level` for maps with monsters and hidden features.
+pub fn lines_to_terrain<I, S>(lines: I) -> Result<TerrainGrid, MapParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let rows: Vec<String> = lines.into_iter().map(|l| l.as_ref().to_string()).collect();
+    let (entries, width, height) = lines_to_legend_entries(&rows, &MapLegend::default())?;
+
+    let mut grid = TerrainGrid::init(width, height);
+    for (i, entry) in entries.into_iter().enumerate() {
+        let (x, y) = (i % width, i / width);
+        let class = match entry {
+            LegendEntry::Terrain(class) => class,
+            LegendEntry::Player => TerrainCellClass::Player,
+            _ => {
+                let ch = rows[y].chars().nth(x).unwrap_or(' ');
+                return Err(MapParseError::NotTerrain { ch, x, y });
+            }
+        };
+        if let Some(cell) = grid.get_mut(x, y) {
+            cell.cell_class = class;
+        }
+    }
+
+    Ok(grid)
+}
@@ -0,0 +1,315 @@
+use crate::game::hunger::HungerState;
+use crate::game::player::Player;
+
+/// One field of the status line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatusField {
+    Level,
+    Gold,
+    Hp,
+    Str,
+    Arm,
+    Exp,
+    Hunger,
+}
+
+impl StatusField {
+    /// Every field, in the order they are shown.
+    pub const ALL: [StatusField; 7] = [
+        StatusField::Level,
+        StatusField::Gold,
+        StatusField::Hp,
+        StatusField::Str,
+        StatusField::Arm,
+        StatusField::Exp,
+        StatusField::Hunger,
+    ];
+
+    /// Label shown before the value.
+    pub const fn label(self) -> &'static str {
+        match self {
+            StatusField::Level => "Level: ",
+            StatusField::Gold => "Gold: ",
+            StatusField::Hp => "Hp: ",
+            StatusField::Str => "Str: ",
+            StatusField::Arm => "Arm: ",
+            StatusField::Exp => "Exp: ",
+            StatusField::Hunger => "",
+        }
+    }
+
+    /// Shorter label for a narrow screen.
+    pub const fn short_label(self) -> &'static str {
+        match self {
+            StatusField::Level => "L:",
+            StatusField::Gold => "$",
+            StatusField::Hp => "Hp:",
+            StatusField::Str => "St:",
+            StatusField::Arm => "Ac:",
+            StatusField::Exp => "X:",
+            StatusField::Hunger => "",
+        }
+    }
+}
+
+/// How a piece of the status line is drawn, the way `TerminalSymbol`
+/// names what a map cell looks like and leaves drawing it to the screen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatusStyle {
+    Normal,
+    /// Something the hero should notice soon.
+    Alert,
+}
+
+/* Fields given up, first to last, when even short labels do not fit. */
+const DROP_ORDER: [StatusField; 5] = [
+    StatusField::Exp,
+    StatusField::Arm,
+    StatusField::Gold,
+    StatusField::Str,
+    StatusField::Level,
+];
+
+/// What the status line shows (Rogue's `status`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    pub depth: i32,
+    pub gold: i32,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub strength: i32,
+    pub max_strength: i32,
+    /// Armor as Rogue shows it: higher is better.
+    pub arm: i32,
+    pub level: i32,
+    pub exp: i32,
+    pub hunger: HungerState,
+}
+
+impl Status {
+    /// Read the status of the hero on dungeon level `depth`.
+    pub fn new(player: &Player, depth: i32) -> Self {
+        let stats = player.stats();
+        Self {
+            depth,
+            gold: player.purse,
+            hp: stats.hp,
+            max_hp: stats.max_hp,
+            strength: stats.strength,
+            max_strength: player.max_strength,
+            arm: 10 - player.armor_class(),
+            level: stats.level,
+            exp: stats.exp,
+            hunger: player.hungry_state,
+        }
+    }
+
+    /// A field's value, padded as Rogue pads it unless `short` is set.
+    pub fn value(&self, field: StatusField, short: bool) -> String {
+        match (field, short) {
+            (StatusField::Level, _) => self.depth.to_string(),
+            (StatusField::Gold, false) => format!("{:<5}", self.gold),
+            (StatusField::Gold, true) => self.gold.to_string(),
+            (StatusField::Hp, false) => {
+                let width = self.max_hp.to_string().len();
+                format!("{:>w$}({:>w$})", self.hp, self.max_hp, w = width)
+            }
+            (StatusField::Hp, true) => format!("{}({})", self.hp, self.max_hp),
+            (StatusField::Str, false) => format!("{:>2}({})", self.strength, self.max_strength),
+            (StatusField::Str, true) => format!("{}({})", self.strength, self.max_strength),
+            (StatusField::Arm, false) => format!("{:<2}", self.arm),
+            (StatusField::Arm, true) => self.arm.to_string(),
+            (StatusField::Exp, _) => format!("{}/{}", self.level, self.exp),
+            (StatusField::Hunger, _) => self.hunger.status_name().to_string(),
+        }
+    }
+
+    /// How a field is drawn: hit points down to a third or less, and any
+    /// hunger, stand out.
+    pub fn style(&self, field: StatusField) -> StatusStyle {
+        let alert = match field {
+            StatusField::Hp => self.hp <= self.max_hp / 3,
+            StatusField::Hunger => self.hunger != HungerState::NotHungry,
+            _ => false,
+        };
+        if alert {
+            StatusStyle::Alert
+        } else {
+            StatusStyle::Normal
+        }
+    }
+
+    /// The line, fit into `width` columns: with short labels if the long
+    /// ones do not fit, then without the least needed fields, then cut.
+    pub fn render(&self, width: usize) -> String {
+        self.spans(width)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    /// The line as `render` fits it, in pieces each drawn in one style.
+    pub fn spans(&self, width: usize) -> Vec<(String, StatusStyle)> {
+        let (fields, short) = self.fit(width);
+        let sep = if short { " " } else { "  " };
+        let mut spans = Vec::new();
+        for field in fields {
+            let label = if short {
+                field.short_label()
+            } else {
+                field.label()
+            };
+            let text = format!("{}{}", label, self.value(field, short));
+            if text.is_empty() {
+                continue;
+            }
+            if !spans.is_empty() {
+                spans.push((sep.to_string(), StatusStyle::Normal));
+            }
+            spans.push((text, self.style(field)));
+        }
+
+        let mut left = width;
+        for (text, _) in &mut spans {
+            text.truncate(left);
+            left -= text.len();
+        }
+        while let Some((text, _)) = spans.last_mut() {
+            text.truncate(text.trim_end().len());
+            if !text.is_empty() {
+                break;
+            }
+            spans.pop();
+        }
+        spans
+    }
+
+    /// The fields to show in `width` columns, and whether with short labels.
+    fn fit(&self, width: usize) -> (Vec<StatusField>, bool) {
+        let mut fields = StatusField::ALL.to_vec();
+        if self.line(&fields, false).len() <= width {
+            return (fields, false);
+        }
+        for drop in DROP_ORDER {
+            if self.line(&fields, true).len() <= width {
+                break;
+            }
+            fields.retain(|&f| f != drop);
+        }
+        (fields, true)
+    }
+
+    /// The given fields with their labels, long or short.
+    fn line(&self, fields: &[StatusField], short: bool) -> String {
+        let sep = if short { " " } else { "  " };
+        fields
+            .iter()
+            .map(|&f| {
+                let label = if short { f.short_label() } else { f.label() };
+                format!("{}{}", label, self.value(f, short))
+            })
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(sep)
+            .trim_end()
+            .to_string()
+    }
+}
+
+/// The bottom line of the screen, redrawn only when something on it
+/// changes.
+#[derive(Debug, Clone, Default)]
+pub struct StatusLine {
+    /// What is on screen now, and how wide the screen was.
+    shown: Option<(Status, usize)>,
+}
+
+impl StatusLine {
+    /// The line to draw, if it is not what the screen already shows.
+    pub fn update(&mut self, status: Status, width: usize) -> Option<Vec<(String, StatusStyle)>> {
+        if self.shown.as_ref() == Some(&(status.clone(), width)) {
+            return None;
+        }
+        let line = status.spans(width);
+        self.shown = Some((status, width));
+        Some(line)
+    }
+
+    /// Draw the line again next time, as after the screen is cleared.
+    pub fn invalidate(&mut self) {
+        self.shown = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status() -> Status {
+        Status {
+            depth: 3,
+            gold: 120,
+            hp: 12,
+            max_hp: 20,
+            strength: 16,
+            max_strength: 16,
+            arm: 4,
+            level: 2,
+            exp: 25,
+            hunger: HungerState::Hungry,
+        }
+    }
+
+    #[test]
+    fn a_wide_screen_gets_the_full_line() {
+        assert_eq!(
+            status().render(80),
+            "Level: 3  Gold: 120    Hp: 12(20)  Str: 16(16)  Arm: 4   Exp: 2/25  Hungry"
+        );
+    }
+
+    #[test]
+    fn narrow_screens_get_short_labels_then_fewer_fields() {
+        let status = status();
+        assert_eq!(
+            status.render(50),
+            "L:3 $120 Hp:12(20) St:16(16) Ac:4 X:2/25 Hungry"
+        );
+        assert_eq!(status.render(30), "L:3 Hp:12(20) St:16(16) Hungry");
+        assert_eq!(status.render(20), "L:3 Hp:12(20) Hungry");
+        assert_eq!(status.render(16), "Hp:12(20) Hungry");
+        assert_eq!(status.render(5), "Hp:12");
+        for width in 0..90 {
+            assert!(status.render(width).len() <= width);
+        }
+    }
+
+    #[test]
+    fn low_hit_points_and_hunger_stand_out() {
+        let mut status = status();
+        let alerts = |status: &Status| -> Vec<String> {
+            status
+                .spans(80)
+                .into_iter()
+                .filter(|(_, style)| *style == StatusStyle::Alert)
+                .map(|(text, _)| text)
+                .collect()
+        };
+        assert_eq!(alerts(&status), ["Hungry"]);
+        status.hp = 6;
+        status.hunger = HungerState::NotHungry;
+        assert_eq!(alerts(&status), ["Hp:  6(20)"]);
+        let text: String = status.spans(80).into_iter().map(|(t, _)| t).collect();
+        assert_eq!(text, status.render(80));
+    }
+
+    #[test]
+    fn the_line_is_redrawn_only_when_it_changes() {
+        let mut line = StatusLine::default();
+        assert!(line.update(status(), 80).is_some());
+        assert!(line.update(status(), 80).is_none());
+        assert!(line.update(status(), 60).is_some());
+        line.invalidate();
+        assert!(line.update(status(), 60).is_some());
+    }
+}
@@ -48,6 +48,57 @@ impl TerminalSymbol {
         }
     }
 
+    /// Find the symbol drawn with the given character, if any.
+    pub const fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            ' ' => Some(TerminalSymbol::Empty),
+            '#' => Some(TerminalSymbol::Passage),
+            '+' => Some(TerminalSymbol::Door),
+            '.' => Some(TerminalSymbol::Floor),
+            '@' => Some(TerminalSymbol::Player),
+            '^' => Some(TerminalSymbol::Trap),
+            '%' => Some(TerminalSymbol::Stairs),
+            '*' => Some(TerminalSymbol::Gold),
+            '!' => Some(TerminalSymbol::Potion),
+            '?' => Some(TerminalSymbol::Scroll),
+            '$' => Some(TerminalSymbol::Magic),
+            ':' => Some(TerminalSymbol::Food),
+            ')' => Some(TerminalSymbol::Weapon),
+            ']' => Some(TerminalSymbol::Armor),
+            ',' => Some(TerminalSymbol::Amulet),
+            '=' => Some(TerminalSymbol::Ring),
+            '/' => Some(TerminalSymbol::Stick),
+            '-' => Some(TerminalSymbol::WallHorizontal),
+            '|' => Some(TerminalSymbol::WallVertical),
+            _ => None,
+        }
+    }
+
+    /// Transfer a terminal symbol back to the terrain cell class it shows.
+    pub const fn to_terrain_cell_class(self) -> TerrainCellClass {
+        match self {
+            TerminalSymbol::Empty => TerrainCellClass::Empty,
+            TerminalSymbol::Passage => TerrainCellClass::Passage,
+            TerminalSymbol::Door => TerrainCellClass::Door,
+            TerminalSymbol::Floor => TerrainCellClass::Floor,
+            TerminalSymbol::Player => TerrainCellClass::Player,
+            TerminalSymbol::Trap => TerrainCellClass::Trap,
+            TerminalSymbol::Stairs => TerrainCellClass::Stairs,
+            TerminalSymbol::Gold => TerrainCellClass::Gold,
+            TerminalSymbol::Potion => TerrainCellClass::Potion,
+            TerminalSymbol::Scroll => TerrainCellClass::Scroll,
+            TerminalSymbol::Magic => TerrainCellClass::Magic,
+            TerminalSymbol::Food => TerrainCellClass::Food,
+            TerminalSymbol::Weapon => TerrainCellClass::Weapon,
+            TerminalSymbol::Armor => TerrainCellClass::Armor,
+            TerminalSymbol::Amulet => TerrainCellClass::Amulet,
+            TerminalSymbol::Ring => TerrainCellClass::Ring,
+            TerminalSymbol::Stick => TerrainCellClass::Stick,
+            TerminalSymbol::WallHorizontal => TerrainCellClass::WallHorizontal,
+            TerminalSymbol::WallVertical => TerrainCellClass::WallVertical,
+        }
+    }
+
     /// Transfer a terrain cell class to a terminal symbol.
     pub const fn from_terrain_cell_class(class: TerrainCellClass) -> Self {
         match class {
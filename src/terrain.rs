@@ -1,2 +1,3 @@
 pub mod terrain_cell;
-pub mod terrain_grid;
\ No newline at end of file
+pub mod terrain_grid;
+pub mod pathfinding;
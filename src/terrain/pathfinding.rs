@@ -0,0 +1,379 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use glam::IVec2;
+
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::terrain::terrain_grid::TerrainGrid;
+
+/// Distance of a cell no source can reach.
+pub const UNREACHED: u32 = u32::MAX;
+
+/// The eight directions, orthogonal ones first.
+const DIRECTIONS: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, -1),
+    IVec2::new(1, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// When a diagonal step is allowed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagonalRule {
+    /// Only orthogonal steps.
+    Never,
+    /// Any diagonal step between two walkable cells.
+    Always,
+    /// Rogue's rules: no diagonal step into or out of a doorway, and no
+    /// squeezing past a wall corner (`diag_ok`).
+    #[default]
+    Rogue,
+}
+
+fn class_at(grid: &TerrainGrid, pos: IVec2) -> Option<TerrainCellClass> {
+    if pos.x < 0 || pos.y < 0 || pos.x as usize >= grid.width || pos.y as usize >= grid.height {
+        return None;
+    }
+    grid.get(pos.x as usize, pos.y as usize)
+        .map(|c| c.cell_class)
+}
+
+/// Whether a position is on the grid and can be walked on (Rogue's `step_ok`).
+pub fn is_walkable(grid: &TerrainGrid, pos: IVec2) -> bool {
+    if pos.x < 0 || pos.y < 0 || pos.x as usize >= grid.width || pos.y as usize >= grid.height {
+        return false;
+    }
+    grid.get(pos.x as usize, pos.y as usize)
+        .is_some_and(|c| c.is_walkable())
+}
+
+/// Whether a single step from `from` to the neighbouring `to` is allowed
+/// under `rule`. Both cells must be walkable.
+pub fn can_step(grid: &TerrainGrid, from: IVec2, to: IVec2, rule: DiagonalRule) -> bool {
+    if !is_walkable(grid, to) || !is_walkable(grid, from) {
+        return false;
+    }
+    if from.x == to.x || from.y == to.y {
+        return true;
+    }
+    match rule {
+        DiagonalRule::Never => false,
+        DiagonalRule::Always => true,
+        DiagonalRule::Rogue => {
+            let door = |p| class_at(grid, p) == Some(TerrainCellClass::Door);
+            !door(from)
+                && !door(to)
+                && is_walkable(grid, IVec2::new(from.x, to.y))
+                && is_walkable(grid, IVec2::new(to.x, from.y))
+        }
+    }
+}
+
+/// Steps from every cell to the nearest of a set of sources, as built by
+/// `Pathfinder::distance_map`. Monsters chase by stepping downhill on it.
+#[derive(Debug, Clone, Default)]
+pub struct DistanceMap {
+    pub width: usize,
+    pub height: usize,
+    pub distances: Vec<u32>,
+}
+
+impl DistanceMap {
+    /// Steps from `pos` to the nearest source, or `None` if it cannot be
+    /// reached or is off the map.
+    pub fn get(&self, pos: IVec2) -> Option<u32> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return None;
+        }
+        let d = self.distances[pos.y as usize * self.width + pos.x as usize];
+        (d != UNREACHED).then_some(d)
+    }
+
+    /// The neighbour of `pos` that is one step closer to a source, if any.
+    pub fn downhill(&self, grid: &TerrainGrid, pos: IVec2, rule: DiagonalRule) -> Option<IVec2> {
+        let here = self.get(pos)?;
+        DIRECTIONS
+            .iter()
+            .map(|&d| pos + d)
+            .filter(|&n| can_step(grid, pos, n, rule))
+            .filter_map(|n| self.get(n).map(|d| (d, n)))
+            .filter(|&(d, _)| d < here)
+            .min_by_key(|&(d, _)| d)
+            .map(|(_, n)| n)
+    }
+}
+
+/// Route finder over a terrain grid.
+///
+/// It keeps its working buffers between searches, so one `Pathfinder` per
+/// level (or per caller) avoids allocating on every monster move. Every step
+/// costs one turn, diagonal or not, as in Rogue.
+#[derive(Debug, Default)]
+pub struct Pathfinder {
+    pub rule: DiagonalRule,
+    /// Search generation each cell was last touched in; cells from older
+    /// searches count as untouched, so the buffers never need clearing.
+    stamp: Vec<u32>,
+    generation: u32,
+    cost: Vec<u32>,
+    came_from: Vec<usize>,
+    open: BinaryHeap<Reverse<(u32, u32, usize)>>,
+    frontier: VecDeque<usize>,
+}
+
+impl Pathfinder {
+    pub fn new(rule: DiagonalRule) -> Self {
+        Pathfinder {
+            rule,
+            ..Pathfinder::default()
+        }
+    }
+
+    /// Start a new search over `grid`, growing the buffers if needed.
+    fn begin(&mut self, grid: &TerrainGrid) {
+        let len = grid.width * grid.height;
+        if self.stamp.len() < len {
+            self.stamp.resize(len, 0);
+            self.cost.resize(len, 0);
+            self.came_from.resize(len, 0);
+        }
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            // Wrapped around: old stamps could look current, so clear them.
+            self.stamp.iter_mut().for_each(|s| *s = 0);
+            self.generation = 1;
+        }
+        self.open.clear();
+        self.frontier.clear();
+    }
+
+    fn cost_of(&self, idx: usize) -> u32 {
+        if self.stamp[idx] == self.generation {
+            self.cost[idx]
+        } else {
+            UNREACHED
+        }
+    }
+
+    /// Lower bound on the steps between two points under this rule.
+    fn heuristic(&self, a: IVec2, b: IVec2) -> u32 {
+        let d = (a - b).abs();
+        match self.rule {
+            DiagonalRule::Never => (d.x + d.y) as u32,
+            _ => d.x.max(d.y) as u32,
+        }
+    }
+
+    /// Shortest path from `start` to `goal` with A*, excluding `start` and
+    /// ending with `goal`. `None` if there is no way through.
+    pub fn find_path(
+        &mut self,
+        grid: &TerrainGrid,
+        start: IVec2,
+        goal: IVec2,
+    ) -> Option<Vec<IVec2>> {
+        self.find_path_avoiding(grid, start, goal, |_| false)
+    }
+
+    /// Like `find_path`, but never steps on a cell for which `blocked`
+    /// returns true, such as one holding another monster. The goal itself
+    /// is always allowed.
+    pub fn find_path_avoiding<F>(
+        &mut self,
+        grid: &TerrainGrid,
+        start: IVec2,
+        goal: IVec2,
+        blocked: F,
+    ) -> Option<Vec<IVec2>>
+    where
+        F: Fn(IVec2) -> bool,
+    {
+        if !is_walkable(grid, start) || !is_walkable(grid, goal) {
+            return None;
+        }
+        self.begin(grid);
+        let width = grid.width;
+        let index = |p: IVec2| p.y as usize * width + p.x as usize;
+        let position = |i: usize| IVec2::new((i % width) as i32, (i / width) as i32);
+
+        let (start_idx, goal_idx) = (index(start), index(goal));
+        self.stamp[start_idx] = self.generation;
+        self.cost[start_idx] = 0;
+        self.open
+            .push(Reverse((self.heuristic(start, goal), 0, start_idx)));
+
+        while let Some(Reverse((_, cost, idx))) = self.open.pop() {
+            if idx == goal_idx {
+                let mut path = Vec::with_capacity(cost as usize);
+                let mut at = goal_idx;
+                while at != start_idx {
+                    path.push(position(at));
+                    at = self.came_from[at];
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if cost > self.cost_of(idx) {
+                continue; // stale entry
+            }
+
+            let pos = position(idx);
+            for d in DIRECTIONS {
+                let next = pos + d;
+                if !can_step(grid, pos, next, self.rule) || (next != goal && blocked(next)) {
+                    continue;
+                }
+                let next_idx = index(next);
+                let next_cost = cost + 1;
+                if next_cost < self.cost_of(next_idx) {
+                    self.stamp[next_idx] = self.generation;
+                    self.cost[next_idx] = next_cost;
+                    self.came_from[next_idx] = idx;
+                    let priority = next_cost + self.heuristic(next, goal);
+                    self.open.push(Reverse((priority, next_cost, next_idx)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Fill `map` with the steps from every cell to the nearest of
+    /// `sources`. `map` is resized to the grid and can be reused.
+    ///
+    /// Since every step costs the same, Dijkstra's search is done as a
+    /// breadth-first flood.
+    pub fn distance_map(&mut self, grid: &TerrainGrid, sources: &[IVec2], map: &mut DistanceMap) {
+        self.begin(grid);
+        map.width = grid.width;
+        map.height = grid.height;
+        map.distances.clear();
+        map.distances.resize(grid.width * grid.height, UNREACHED);
+
+        let width = grid.width;
+        let index = |p: IVec2| p.y as usize * width + p.x as usize;
+        for &source in sources {
+            if is_walkable(grid, source) && map.distances[index(source)] == UNREACHED {
+                map.distances[index(source)] = 0;
+                self.frontier.push_back(index(source));
+            }
+        }
+
+        while let Some(idx) = self.frontier.pop_front() {
+            let pos = IVec2::new((idx % width) as i32, (idx / width) as i32);
+            let next_distance = map.distances[idx] + 1;
+            for d in DIRECTIONS {
+                let next = pos + d;
+                if !can_step(grid, pos, next, self.rule) {
+                    continue;
+                }
+                let next_idx = index(next);
+                if map.distances[next_idx] == UNREACHED {
+                    map.distances[next_idx] = next_distance;
+                    self.frontier.push_back(next_idx);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon_level::level_parser::parse_dungeon_level;
+    use crate::rng::GameRng;
+    use crate::terminal::parse_terrain::MapLegend;
+
+    /// Two rooms joined by a passage, one with a door still hidden.
+    fn grid() -> TerrainGrid {
+        let map = [
+            "-----      ",
+            "|...+#     ",
+            "|...|#     ",
+            "-----#     ",
+            "   --+--&--",
+            "   |......|",
+            "   --------",
+        ];
+        let legend = MapLegend::default();
+        parse_dungeon_level(map, &legend, 1, &mut GameRng::new(3))
+            .unwrap()
+            .level
+            .terrain
+    }
+
+    fn is_door(grid: &TerrainGrid, pos: IVec2) -> bool {
+        class_at(grid, pos) == Some(TerrainCellClass::Door)
+    }
+
+    #[test]
+    fn rogue_paths_go_straight_through_doors() {
+        let grid = grid();
+        let (start, goal) = (IVec2::new(2, 2), IVec2::new(5, 5));
+        let path = Pathfinder::new(DiagonalRule::Rogue)
+            .find_path(&grid, start, goal)
+            .unwrap();
+        let mut from = start;
+        for &to in &path {
+            assert!(can_step(&grid, from, to, DiagonalRule::Rogue));
+            if is_door(&grid, from) || is_door(&grid, to) {
+                assert!(from.x == to.x || from.y == to.y, "{} -> {}", from, to);
+            }
+            from = to;
+        }
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(path.len(), 7);
+
+        let shortcut = Pathfinder::new(DiagonalRule::Always)
+            .find_path(&grid, start, goal)
+            .unwrap();
+        assert!(shortcut.len() < path.len());
+    }
+
+    #[test]
+    fn hidden_doors_and_walls_block_the_way() {
+        let grid = grid();
+        assert!(!is_walkable(&grid, IVec2::new(9, 4)));
+        assert!(!is_walkable(&grid, IVec2::new(0, 1)));
+        assert!(!is_walkable(&grid, IVec2::new(-1, 1)));
+        assert!(!can_step(
+            &grid,
+            IVec2::new(4, 1),
+            IVec2::new(5, 2),
+            DiagonalRule::Rogue
+        ));
+        let mut finder = Pathfinder::new(DiagonalRule::Rogue);
+        assert_eq!(
+            finder.find_path(&grid, IVec2::new(2, 2), IVec2::new(9, 4)),
+            None
+        );
+    }
+
+    #[test]
+    fn distance_maps_lead_downhill_to_the_source() {
+        let grid = grid();
+        let mut map = DistanceMap::default();
+        let source = IVec2::new(5, 5);
+        Pathfinder::new(DiagonalRule::Rogue).distance_map(&grid, &[source], &mut map);
+
+        assert_eq!(map.get(source), Some(0));
+        assert_eq!(map.get(IVec2::new(5, 4)), Some(1));
+        assert_eq!(map.get(IVec2::new(4, 1)), Some(5));
+        assert_eq!(map.get(IVec2::new(0, 0)), None);
+        assert_eq!(map.get(IVec2::new(20, 0)), None);
+
+        let mut pos = IVec2::new(1, 2);
+        let mut steps = 0;
+        while let Some(next) = map.downhill(&grid, pos, DiagonalRule::Rogue) {
+            assert_eq!(map.get(next), map.get(pos).map(|d| d - 1));
+            pos = next;
+            steps += 1;
+        }
+        assert_eq!(pos, source);
+        assert_eq!(Some(steps), map.get(IVec2::new(1, 2)));
+    }
+}
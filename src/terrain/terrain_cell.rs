@@ -1,6 +1,16 @@
 use crate::thing::Thing;
 
+/* Flags for the level map. */
+pub const F_PASS: u8 = 0x80; /* is a passageway */
+pub const F_SEEN: u8 = 0x40; /* have seen this spot before */
+pub const F_DROPPED: u8 = 0x20; /* object was dropped here */
+pub const F_LOCKED: u8 = 0x20; /* door is locked */
+pub const F_SECRET: u8 = 0x10; /* secret door or passage not found yet (Rogue's !F_REAL) */
+pub const F_PNUM: u8 = 0x0f; /* passage number mask */
+pub const F_TMASK: u8 = 0x07; /* trap number mask */
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TerrainCellClass {
     Empty,
     Passage,
@@ -23,7 +33,20 @@ pub enum TerrainCellClass {
     WallVertical,
 }
 
+impl TerrainCellClass {
+    /// Whether a creature can step onto a cell of this class (Rogue's `step_ok`).
+    pub const fn is_walkable(self) -> bool {
+        !matches!(
+            self,
+            TerrainCellClass::Empty
+                | TerrainCellClass::WallHorizontal
+                | TerrainCellClass::WallVertical
+        )
+    }
+}
+
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TerrainCell {
     pub cell_class: TerrainCellClass,
     pub flags: u8,
@@ -50,4 +73,10 @@ impl TerrainCell {
             monst,
         }
     }
+
+    /// Whether a creature can step onto this cell: secret doors and
+    /// passages stay solid until found.
+    pub const fn is_walkable(&self) -> bool {
+        self.cell_class.is_walkable() && self.flags & F_SECRET == 0
+    }
 }
@@ -1,5 +1,6 @@
 use crate::terrain::terrain_cell::{TerrainCell, TerrainCellClass};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TerrainGrid {
     pub width: usize,
     pub height: usize,
@@ -2,6 +2,7 @@ use glam::IVec2;
 
 /// Represents a passage connecting two rooms by index in the room array.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Passage {
     /// Index of the first connected room in the rooms slice.
     pub room_a: usize,
@@ -2,7 +2,18 @@ use crate::terrain::terrain_cell::TerrainCellClass;
 use crate::terrain::terrain_grid::TerrainGrid;
 use glam::IVec2;
 
+/// What a room is for, beyond plain floor space.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoomKind {
+    #[default]
+    Normal,
+    /// Stuffed with extra objects and guarded by monsters from the next level down.
+    Treasure,
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Room {
     pub pos: IVec2, // let top corner of the room
     pub size: IVec2,
@@ -10,6 +21,7 @@ pub struct Room {
     pub is_gone: bool,  /* room is gone (a corridor) */
     pub is_dark:bool, /* room is dark */
     pub doors: Vec<IVec2>,
+    pub kind: RoomKind,
 }
 
 pub fn put_room(room: &Room, map: &mut TerrainGrid) {
@@ -40,6 +52,14 @@ fn put_room_walls(room: &Room, map: &mut TerrainGrid) {
     put_horizontal_wall(room.pos.x, room.pos.x + room.size.x - 1, room.pos.y + room.size.y - 1, map);
 }
 
+/// Check whether a position lies inside the room's rectangle, walls included.
+pub fn room_contains(room: &Room, pos: IVec2) -> bool {
+    pos.x >= room.pos.x
+        && pos.y >= room.pos.y
+        && pos.x < room.pos.x + room.size.x
+        && pos.y < room.pos.y + room.size.y
+}
+
 fn put_room_floor(room: &Room, map: &mut TerrainGrid) {
     for y in (room.pos.y + 1)..(room.pos.y + room.size.y - 1) {
         for x in (room.pos.x + 1)..(room.pos.x + room.size.x - 1) {
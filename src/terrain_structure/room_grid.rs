@@ -32,10 +32,17 @@ pub const POSSIBLE_ADJACENT: [[bool; MAX_ROOMS]; MAX_ROOMS] = [
 /// You can rebuild this from scratch when generating a level
 /// to mirror the `isconn` arrays in the C code.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoomAdjacency {
     pub connected: [[bool; MAX_ROOMS]; MAX_ROOMS],
 }
 
+impl Default for RoomAdjacency {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RoomAdjacency {
     /// Create a new adjacency matrix with no realized connections.
     pub fn new() -> Self {
@@ -65,6 +72,13 @@ impl RoomAdjacency {
         a < MAX_ROOMS && b < MAX_ROOMS && self.connected[a][b]
     }
 
+    /// Number of realized connections, each pair counted once.
+    pub fn edge_count(&self) -> usize {
+        (0..MAX_ROOMS)
+            .map(|a| (a + 1..MAX_ROOMS).filter(|&b| self.connected[a][b]).count())
+            .sum()
+    }
+
     /// Generate a random room connection graph.
     ///
     /// This mirrors Rogue's `do_passages`:
@@ -118,8 +132,8 @@ impl RoomAdjacency {
 
             // Collect neighbors this room could connect to but isn't yet.
             let mut candidates = Vec::new();
-            for to in 0..MAX_ROOMS {
-                if POSSIBLE_ADJACENT[from][to] && !adj.is_connected(from, to) {
+            for (to, &possible) in POSSIBLE_ADJACENT[from].iter().enumerate() {
+                if possible && !adj.is_connected(from, to) {
                     candidates.push(to);
                 }
             }
@@ -1,27 +1,82 @@
+pub mod stats;
+pub mod monster_table;
+pub mod object_table;
+pub mod new_thing;
+pub mod chase;
+pub mod fight;
+pub mod pack;
+pub mod knowledge;
+pub mod naming;
+
 use glam::IVec2;
 
+use crate::thing::stats::Stats;
+
+/* Object types (the character the object is drawn with). */
+pub const POTION: i32 = '!' as i32;
+pub const SCROLL: i32 = '?' as i32;
+pub const FOOD: i32 = ':' as i32;
+pub const WEAPON: i32 = ')' as i32;
+pub const ARMOR: i32 = ']' as i32;
+pub const RING: i32 = '=' as i32;
+pub const STICK: i32 = '/' as i32;
+pub const GOLD: i32 = '*' as i32;
+pub const AMULET: i32 = ',' as i32;
+
+/* Flags for objects. */
+pub const IS_CURSED: i32 = 0o000001; /* object is cursed */
+pub const IS_KNOW: i32 = 0o000002; /* player knows details about the object */
+pub const IS_MISL: i32 = 0o000004; /* object is a missile type */
+pub const IS_MANY: i32 = 0o000010; /* object comes in groups */
+pub const IS_PROT: i32 = 0o000040; /* armor is permanently protected */
+
+/* Flags for creatures. */
+pub const CAN_HUH: i32 = 0o000001; /* creature can confuse */
+pub const CAN_SEE: i32 = 0o000002; /* creature can see invisible creatures */
+pub const IS_BLIND: i32 = 0o000004; /* creature is blind */
+pub const IS_CANC: i32 = 0o000010; /* creature has special qualities cancelled */
+pub const IS_LEVIT: i32 = 0o000010; /* hero is levitating */
+pub const IS_FOUND: i32 = 0o000020; /* creature has been seen (used for objects) */
+pub const IS_GREED: i32 = 0o000040; /* creature runs to protect gold */
+pub const IS_HASTE: i32 = 0o000100; /* creature has been hastened */
+pub const IS_TARGET: i32 = 0o000200; /* creature is the target of an 'f' command */
+pub const IS_HELD: i32 = 0o000400; /* creature has been held */
+pub const IS_HUH: i32 = 0o001000; /* creature is confused */
+pub const IS_INVIS: i32 = 0o002000; /* creature is invisible */
+pub const IS_MEAN: i32 = 0o004000; /* creature can wake when player enters room */
+pub const IS_HALU: i32 = 0o004000; /* hero is on acid trip */
+pub const IS_REGEN: i32 = 0o010000; /* creature can regenerate */
+pub const IS_RUN: i32 = 0o020000; /* creature is running at the player */
+pub const SEE_MONST: i32 = 0o040000; /* hero can detect unseen monsters */
+pub const IS_FLY: i32 = 0o040000; /* creature can fly */
+pub const IS_SLOW: i32 = 0o100000; /* creature has been slowed */
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Thing {
     Monster(MonsterThing),
     Object(ObjectThing),
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MonsterThing {
     pub pos: IVec2,
     pub turn: bool,
     pub kind: char,
     pub disguise: char,
     pub old_ch: char,
+    /// Where the monster is heading; `None` means after the hero.
     pub dest: Option<IVec2>,
-    pub flags: i16,
-    //pub stats: Stats,
+    pub flags: i32,
+    pub stats: Stats,
     pub room_id: Option<usize>,
     pub pack: Vec<Thing>, // or Vec<ObjectThing> if you want only objects
     pub reserved: i32,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectThing {
     pub kind: i32,
     pub pos: IVec2,
@@ -38,4 +93,18 @@ pub struct ObjectThing {
     pub flags: i32,
     pub group: i32,
     pub label: Option<String>,
-}
\ No newline at end of file
+}
+
+impl MonsterThing {
+    /// Check whether the given creature flag is set.
+    pub fn on(&self, flag: i32) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+impl ObjectThing {
+    /// Check whether the given object flag is set.
+    pub fn on(&self, flag: i32) -> bool {
+        self.flags & flag != 0
+    }
+}
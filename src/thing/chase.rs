@@ -0,0 +1,477 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::terminal::terminal_symbol::TerminalSymbol;
+use crate::terrain::pathfinding::{can_step, is_walkable, DiagonalRule, Pathfinder};
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::monster_table::monster_info;
+use crate::thing::object_table::S_SCARE;
+use crate::thing::{
+    MonsterThing, Thing, CAN_SEE, GOLD, IS_BLIND, IS_FLY, IS_GREED, IS_HASTE, IS_HELD, IS_HUH,
+    IS_INVIS, IS_LEVIT, IS_MEAN, IS_RUN, IS_SLOW, IS_TARGET, SCROLL,
+};
+
+/// Squared distance within which the hero sees in the dark.
+pub const LAMPDIST: i32 = 3;
+
+/// Squared distance between two points (Rogue's `dist_cp`).
+pub fn dist_cp(a: IVec2, b: IVec2) -> i32 {
+    let d = a - b;
+    d.x * d.x + d.y * d.y
+}
+
+/// Whether the hero can see a monster (Rogue's `see_monst`).
+pub fn see_monst(level: &DungeonLevel, hero: &MonsterThing, mp: &MonsterThing) -> bool {
+    if hero.on(IS_BLIND) {
+        return false;
+    }
+    if mp.on(IS_INVIS) && !hero.on(CAN_SEE) {
+        return false;
+    }
+    if dist_cp(mp.pos, hero.pos) < LAMPDIST {
+        // Close by, but not around a corner.
+        let (y, x) = (mp.pos.y, mp.pos.x);
+        return y == hero.pos.y
+            || x == hero.pos.x
+            || is_walkable(&level.terrain, IVec2::new(hero.pos.x, y))
+            || is_walkable(&level.terrain, IVec2::new(x, hero.pos.y));
+    }
+    let proom = level.room_at(hero.pos);
+    proom.is_some() && mp.room_id == proom && !level.rooms[proom.unwrap()].is_dark
+}
+
+/// Whether a scare monster scroll lies at a position.
+pub fn scare_at(level: &DungeonLevel, pos: IVec2) -> bool {
+    level
+        .objects
+        .iter()
+        .any(|obj| obj.pos == pos && obj.kind == SCROLL && obj.which == S_SCARE)
+}
+
+/// Whether a monster may step from `from` to `to`: the move is legal, no
+/// other monster is there and it is not scared off (Rogue's `diag_ok` and
+/// `step_ok`). The hero's spot counts as free.
+fn monster_step_ok(level: &DungeonLevel, from: IVec2, to: IVec2) -> bool {
+    can_step(&level.terrain, from, to, DiagonalRule::Rogue)
+        && level.monster_at(to).is_none()
+        && !scare_at(level, to)
+}
+
+/// Gold lying in a room, if any.
+fn gold_in_room(level: &DungeonLevel, room_id: usize) -> Option<IVec2> {
+    level
+        .objects
+        .iter()
+        .find(|obj| obj.kind == GOLD && level.room_at(obj.pos) == Some(room_id))
+        .map(|obj| obj.pos)
+}
+
+/// Find the proper destination for the monster (Rogue's `find_dest`):
+/// something lying in its room that it wants to carry off, or else the hero.
+pub fn find_dest<R: Rng + ?Sized>(
+    level: &DungeonLevel,
+    idx: usize,
+    hero: &MonsterThing,
+    rng: &mut R,
+) -> Option<IVec2> {
+    let tp = &level.monsters[idx];
+    let prob = monster_info(tp.kind).carry;
+    if prob <= 0 || tp.room_id == level.room_at(hero.pos) || see_monst(level, hero, tp) {
+        return None;
+    }
+    for obj in &level.objects {
+        if obj.kind == SCROLL && obj.which == S_SCARE {
+            continue;
+        }
+        if level.room_at(obj.pos) == tp.room_id && rnd(rng, 100) < prob {
+            let claimed = level
+                .monsters
+                .iter()
+                .any(|other| other.dest == Some(obj.pos));
+            if !claimed {
+                return Some(obj.pos);
+            }
+        }
+    }
+    None
+}
+
+/// Set the monster at `pos` running after the hero (Rogue's `runto`).
+pub fn runto<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    pos: IVec2,
+    hero: &MonsterThing,
+    rng: &mut R,
+) {
+    let Some(idx) = level.monsters.iter().position(|m| m.pos == pos) else {
+        return;
+    };
+    level.monsters[idx].flags |= IS_RUN;
+    level.monsters[idx].flags &= !IS_HELD;
+    level.monsters[idx].dest = find_dest(level, idx, hero, rng);
+}
+
+/// What to do when the hero steps next to or sees a monster (Rogue's
+/// `wake_monster`): mean monsters may start chasing, and greedy ones go to
+/// guard the gold in the hero's room. Returns the monster's index.
+///
+/// Medusa's gaze is not handled here. A `stealthy` hero, one wearing a ring
+/// of stealth, wakes nothing that is mean.
+pub fn wake_monster<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    pos: IVec2,
+    hero: &MonsterThing,
+    stealthy: bool,
+    rng: &mut R,
+) -> Option<usize> {
+    let idx = level.monsters.iter().position(|m| m.pos == pos)?;
+    let proom = level.room_at(hero.pos);
+    let gold = proom.and_then(|r| gold_in_room(level, r));
+
+    let tp = &mut level.monsters[idx];
+    // Every time he sees a mean monster, it might start chasing him.
+    if !tp.on(IS_RUN)
+        && rnd(rng, 3) != 0
+        && tp.on(IS_MEAN)
+        && !tp.on(IS_HELD)
+        && !stealthy
+        && !hero.on(IS_LEVIT)
+    {
+        tp.dest = None;
+        tp.flags |= IS_RUN;
+    }
+    // Let greedy ones guard gold.
+    if tp.on(IS_GREED) && !tp.on(IS_RUN) {
+        tp.flags |= IS_RUN;
+        tp.dest = gold;
+    }
+    Some(idx)
+}
+
+/// Make all the running monsters move (Rogue's `runners`). Monsters without
+/// `IS_RUN` are asleep and stay put.
+///
+/// Returns the index of every monster that attacks the hero, once per
+/// attack, in the order they happen; the caller resolves the fights.
+pub fn runners<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    hero: &MonsterThing,
+    pathfinder: &mut Pathfinder,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut attacks = Vec::new();
+    for idx in 0..level.monsters.len() {
+        let tp = &level.monsters[idx];
+        if tp.on(IS_HELD) || !tp.on(IS_RUN) {
+            continue;
+        }
+        let orig_pos = tp.pos;
+        let was_target = tp.on(IS_TARGET);
+
+        let mut hits = move_monst(level, idx, hero, pathfinder, rng);
+        let tp = &level.monsters[idx];
+        if tp.on(IS_FLY) && dist_cp(hero.pos, tp.pos) >= 3 {
+            hits += move_monst(level, idx, hero, pathfinder, rng);
+        }
+        attacks.extend(std::iter::repeat_n(idx, hits));
+
+        let tp = &mut level.monsters[idx];
+        if was_target && tp.pos != orig_pos {
+            tp.flags &= !IS_TARGET;
+        }
+    }
+    attacks
+}
+
+/// Execute a single turn of running for a monster (Rogue's `move_monst`).
+/// Slow monsters move every other turn and hasted ones twice. Returns the
+/// number of attacks made on the hero.
+pub fn move_monst<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    idx: usize,
+    hero: &MonsterThing,
+    pathfinder: &mut Pathfinder,
+    rng: &mut R,
+) -> usize {
+    let mut hits = 0;
+    let tp = &level.monsters[idx];
+    if !tp.on(IS_SLOW) || tp.turn {
+        hits += do_chase(level, idx, hero, pathfinder, rng) as usize;
+    }
+    if level.monsters[idx].on(IS_HASTE) {
+        hits += do_chase(level, idx, hero, pathfinder, rng) as usize;
+    }
+    level.monsters[idx].turn ^= true;
+    hits
+}
+
+/// Make one monster chase its destination (Rogue's `do_chase`). Returns
+/// true if it attacks the hero instead of moving.
+///
+/// A monster in the same room as its goal closes in directly. Anywhere else
+/// it follows the shortest route, which leads out through the door toward
+/// the goal rather than Rogue's door nearest as the crow flies, as that one
+/// can be a dead end. Dragons do not breathe fire yet.
+pub fn do_chase<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    idx: usize,
+    hero: &MonsterThing,
+    pathfinder: &mut Pathfinder,
+    rng: &mut R,
+) -> bool {
+    let tp = &level.monsters[idx];
+    let rer = tp.room_id;
+
+    // If the gold has been taken, run after the hero.
+    if tp.on(IS_GREED) && rer.is_none_or(|r| gold_in_room(level, r).is_none()) {
+        level.monsters[idx].dest = None;
+    }
+    let tp = &level.monsters[idx];
+    let goal = tp.dest.unwrap_or(hero.pos);
+    let ree = level.room_at(goal);
+
+    let this = if rer.is_some() && rer == ree {
+        goal
+    } else {
+        // Take the next step of the way there, heading around other
+        // monsters if there is room to.
+        let blocked = |p| level.monster_at(p).is_some();
+        pathfinder
+            .find_path_avoiding(&level.terrain, tp.pos, goal, blocked)
+            .or_else(|| pathfinder.find_path(&level.terrain, tp.pos, goal))
+            .and_then(|path| path.first().copied())
+            .unwrap_or(goal)
+    };
+
+    let (keep_chasing, ch_ret) = chase(level, idx, this, hero, rng);
+    let mut stoprun = false;
+    if !keep_chasing {
+        if this == hero.pos {
+            return true;
+        }
+        if Some(this) == level.monsters[idx].dest {
+            // Pick up what it came for; greedy monsters only stand guard
+            // over gold.
+            let greedy = level.monsters[idx].on(IS_GREED);
+            let wanted = level
+                .objects
+                .iter()
+                .position(|obj| obj.pos == this && !(greedy && obj.kind == GOLD));
+            if let Some(oi) = wanted {
+                let obj = level.objects.remove(oi);
+                let class = if level.room_at(this).is_some() {
+                    TerrainCellClass::Floor
+                } else {
+                    TerrainCellClass::Passage
+                };
+                if let Some(cell) = level.terrain.get_mut(this.x as usize, this.y as usize) {
+                    cell.cell_class = class;
+                }
+                level.monsters[idx].pack.push(Thing::Object(obj));
+                level.monsters[idx].dest = find_dest(level, idx, hero, rng);
+            }
+            if level.monsters[idx].kind != 'F' {
+                stoprun = true;
+            }
+        }
+    } else if level.monsters[idx].kind == 'F' {
+        return false;
+    }
+
+    // Never walk into the hero without meaning to attack.
+    if ch_ret != hero.pos {
+        relocate(level, idx, ch_ret);
+    }
+
+    let tp = &mut level.monsters[idx];
+    if stoprun && Some(tp.pos) == tp.dest {
+        tp.flags &= !IS_RUN;
+    }
+    false
+}
+
+/// Make a monster's move (Rogue's `relocate`).
+pub fn relocate(level: &mut DungeonLevel, idx: usize, new_loc: IVec2) {
+    if level.monsters[idx].pos == new_loc {
+        return;
+    }
+    let room_id = level.room_at(new_loc);
+    let old_ch = level
+        .cell_class_at(new_loc)
+        .map_or(' ', TerminalSymbol::char_from_terrain_cell_class);
+    let tp = &mut level.monsters[idx];
+    tp.room_id = room_id;
+    tp.old_ch = old_ch;
+    tp.pos = new_loc;
+}
+
+/// Find the spot for the chaser to move closer to the chasee (Rogue's
+/// `chase`). Returns whether it wants to keep chasing after this move, and
+/// where to move.
+pub fn chase<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    idx: usize,
+    ee: IVec2,
+    hero: &MonsterThing,
+    rng: &mut R,
+) -> (bool, IVec2) {
+    let tp = &level.monsters[idx];
+    let er = tp.pos;
+    let ch_ret;
+    let curdist;
+
+    // Confused monsters move at random. Invisible stalkers are slightly
+    // confused all of the time, and bats are quite confused all the time.
+    if (tp.on(IS_HUH) && rnd(rng, 5) != 0)
+        || (tp.kind == 'P' && rnd(rng, 5) == 0)
+        || (tp.kind == 'B' && rnd(rng, 2) == 0)
+    {
+        ch_ret = rndmove(level, idx, rng);
+        curdist = dist_cp(ch_ret, ee);
+        // Small chance that it will become un-confused.
+        if rnd(rng, 20) == 0 {
+            level.monsters[idx].flags &= !IS_HUH;
+        }
+    } else {
+        // Find the empty spot next to the chaser closest to the chasee. If
+        // there is none, stay where we are.
+        let mut best = er;
+        let mut bestdist = dist_cp(er, ee);
+        let mut plcnt = 1;
+        for x in er.x - 1..=er.x + 1 {
+            for y in er.y - 1..=er.y + 1 {
+                let tryp = IVec2::new(x, y);
+                if tryp == er || !monster_step_ok(level, er, tryp) {
+                    continue;
+                }
+                let thisdist = dist_cp(tryp, ee);
+                if thisdist < bestdist {
+                    plcnt = 1;
+                    best = tryp;
+                    bestdist = thisdist;
+                } else if thisdist == bestdist {
+                    plcnt += 1;
+                    if rnd(rng, plcnt) == 0 {
+                        best = tryp;
+                    }
+                }
+            }
+        }
+        ch_ret = best;
+        curdist = bestdist;
+    }
+
+    (curdist != 0 && ch_ret != hero.pos, ch_ret)
+}
+
+/// Move a monster in a random direction, or not at all if that way is
+/// blocked (Rogue's `rndmove`).
+pub fn rndmove<R: Rng + ?Sized>(level: &DungeonLevel, idx: usize, rng: &mut R) -> IVec2 {
+    let pos = level.monsters[idx].pos;
+    let dy = rnd(rng, 3) - 1;
+    let dx = rnd(rng, 3) - 1;
+    let ret = pos + IVec2::new(dx, dy);
+    if ret == pos || !monster_step_ok(level, pos, ret) {
+        return pos;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon_level::level_parser::parse_dungeon_level;
+    use crate::game::player::Player;
+    use crate::rng::GameRng;
+    use crate::terminal::parse_terrain::MapLegend;
+    use crate::thing::monster_table::new_monster;
+    use crate::thing::ObjectThing;
+
+    const MAP: [&str; 7] = [
+        "---------      ",
+        "|.......+####  ",
+        "|.......|   #  ",
+        "---------   #  ",
+        "         ---+--",
+        "         |....|",
+        "         ------",
+    ];
+
+    /// The level with a running kestrel at `monster` and the hero at `hero`.
+    fn setup(monster: IVec2, hero: IVec2, rng: &mut GameRng) -> (DungeonLevel, MonsterThing) {
+        let mut level = parse_dungeon_level(MAP, &MapLegend::default(), 1, rng)
+            .unwrap()
+            .level;
+        let mut tp = new_monster('K', monster, 1, rng);
+        tp.flags |= IS_RUN;
+        tp.room_id = level.room_at(monster);
+        level.monsters.push(tp);
+        let mut hero = Player::new(hero).creature;
+        hero.room_id = level.room_at(hero.pos);
+        (level, hero)
+    }
+
+    #[test]
+    fn closes_in_across_a_room_then_attacks() {
+        let mut rng = GameRng::new(5);
+        let (mut level, hero) = setup(IVec2::new(6, 2), IVec2::new(1, 1), &mut rng);
+        let mut pathfinder = Pathfinder::default();
+
+        let before = dist_cp(level.monsters[0].pos, hero.pos);
+        assert!(!do_chase(&mut level, 0, &hero, &mut pathfinder, &mut rng));
+        assert!(dist_cp(level.monsters[0].pos, hero.pos) < before);
+
+        while !do_chase(&mut level, 0, &hero, &mut pathfinder, &mut rng) {}
+        let pos = level.monsters[0].pos;
+        assert_eq!((pos - hero.pos).abs().max_element(), 1);
+    }
+
+    #[test]
+    fn leaves_a_room_straight_through_the_door() {
+        let mut rng = GameRng::new(9);
+        let (mut level, hero) = setup(IVec2::new(2, 2), IVec2::new(11, 5), &mut rng);
+        let mut pathfinder = Pathfinder::default();
+
+        let mut trail = vec![level.monsters[0].pos];
+        for _ in 0..30 {
+            if do_chase(&mut level, 0, &hero, &mut pathfinder, &mut rng) {
+                break;
+            }
+            trail.push(level.monsters[0].pos);
+        }
+        assert!(trail.contains(&IVec2::new(8, 1)));
+        assert!(trail.contains(&IVec2::new(12, 4)));
+        for step in trail.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            assert!(can_step(&level.terrain, from, to, DiagonalRule::Rogue));
+        }
+        assert_eq!(level.monsters[0].room_id, Some(1));
+    }
+
+    #[test]
+    fn will_not_step_on_a_scare_monster_scroll() {
+        let scroll = IVec2::new(2, 1);
+        for seed in 0..20 {
+            let mut rng = GameRng::new(seed);
+            let (mut level, hero) = setup(IVec2::new(3, 1), IVec2::new(1, 1), &mut rng);
+            let mut obj = ObjectThing::new(SCROLL, S_SCARE);
+            obj.pos = scroll;
+            level.objects.push(obj);
+            level.monsters[0].flags &= !IS_HUH;
+            let (_, to) = chase(&mut level, 0, hero.pos, &hero, &mut rng);
+            assert_ne!(to, scroll);
+        }
+    }
+
+    #[test]
+    fn sleeping_monsters_stay_put() {
+        let mut rng = GameRng::new(2);
+        let (mut level, hero) = setup(IVec2::new(6, 2), IVec2::new(1, 1), &mut rng);
+        level.monsters[0].flags &= !IS_RUN;
+        let attacks = runners(&mut level, &hero, &mut Pathfinder::default(), &mut rng);
+        assert!(attacks.is_empty());
+        assert_eq!(level.monsters[0].pos, IVec2::new(6, 2));
+    }
+}
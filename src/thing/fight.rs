@@ -0,0 +1,630 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::{rnd, roll};
+use crate::dungeon_level::dungeon::Dungeon;
+use crate::dungeon_level::dungeon_level::DungeonLevel;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::chase::{runto, see_monst};
+use crate::thing::knowledge::pick_color;
+use crate::thing::monster_table::monster_info;
+use crate::thing::object_table::{object_cell_class, WEAP_INFO};
+use crate::thing::stats::Stats;
+use crate::thing::{
+    MonsterThing, ObjectThing, Thing, CAN_HUH, GOLD, IS_BLIND, IS_CANC, IS_HALU, IS_HELD, IS_HUH,
+    IS_MISL, IS_RUN, WEAPON,
+};
+
+/* Saving throws. */
+pub const VS_POISON: i32 = 0;
+pub const VS_PARALYZATION: i32 = 0;
+pub const VS_DEATH: i32 = 0;
+pub const VS_BREATH: i32 = 2;
+pub const VS_MAGIC: i32 = 3;
+
+/// Adjustments to hit probabilities due to strength.
+pub const STR_PLUS: [i32; 32] = [
+    -7, -6, -5, -4, -3, -2, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 2, 3,
+];
+
+/// Adjustments to damage done due to strength.
+pub const ADD_DAM: [i32; 32] = [
+    -7, -6, -5, -4, -3, -2, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 3, 3, 4, 5, 5, 5, 5, 5, 5, 5,
+    5, 5, 6,
+];
+
+/// To-hit bonus for a strength.
+pub fn str_plus(strength: i32) -> i32 {
+    STR_PLUS[strength.clamp(0, 31) as usize]
+}
+
+/// Damage bonus for a strength.
+pub fn add_dam(strength: i32) -> i32 {
+    ADD_DAM[strength.clamp(0, 31) as usize]
+}
+
+/// Gold dropped by a leprechaun on a level (Rogue's `GOLDCALC`).
+pub fn goldcalc<R: Rng + ?Sized>(depth: i32, rng: &mut R) -> i32 {
+    rnd(rng, 50 + 10 * depth) + 2
+}
+
+/// Returns true if the swing hits (Rogue's `swing`).
+pub fn swing<R: Rng + ?Sized>(at_lvl: i32, op_arm: i32, wplus: i32, rng: &mut R) -> bool {
+    let res = rnd(rng, 20);
+    let need = (20 - at_lvl) - op_arm;
+    res + wplus >= need
+}
+
+/// See if a creature saves against something (Rogue's `save_throw`).
+pub fn save_throw<R: Rng + ?Sized>(which: i32, stats: &Stats, rng: &mut R) -> bool {
+    let need = 14 + which - stats.level / 2;
+    roll(rng, 1, 20) >= need
+}
+
+/// What the hero attacks with.
+#[derive(Debug, Clone, Copy)]
+pub enum Weapon<'a> {
+    /// Bare hands: the attacker's own damage.
+    Hands,
+    /// A weapon swung in melee.
+    Wielded(&'a ObjectThing),
+    /// Something thrown or fired, and what the thrower is wielding, which
+    /// matters when it is the launcher for the missile.
+    Thrown {
+        missile: &'a ObjectThing,
+        wielded: Option<&'a ObjectThing>,
+    },
+}
+
+/// To-hit and damage bonuses the hero gets from rings, which only count
+/// for the weapon in hand (Rogue's `roll_em`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RingBonus {
+    pub hplus: i32,
+    pub dplus: i32,
+}
+
+/// The damage string and bonuses one attack is made with.
+#[derive(Debug, Clone, Copy)]
+pub struct Blow<'a> {
+    pub damage: &'a str,
+    pub hplus: i32,
+    pub dplus: i32,
+}
+
+impl<'a> Blow<'a> {
+    /// Work out the blow an attacker strikes with a weapon, including
+    /// hurled damage and launcher bonuses for missiles.
+    pub fn new(att: &'a Stats, weapon: Weapon<'a>) -> Self {
+        match weapon {
+            Weapon::Hands => Blow {
+                damage: &att.damage,
+                hplus: 0,
+                dplus: 0,
+            },
+            Weapon::Wielded(weap) => Blow {
+                damage: &weap.damage,
+                hplus: weap.hplus,
+                dplus: weap.dplus,
+            },
+            Weapon::Thrown { missile, wielded } => {
+                let mut blow = Blow {
+                    damage: &missile.damage,
+                    hplus: missile.hplus,
+                    dplus: missile.dplus,
+                };
+                let launcher = wielded.filter(|w| w.which == missile.launch);
+                if let (true, Some(launcher)) = (missile.on(IS_MISL), launcher) {
+                    blow.damage = &missile.hurl_dmg;
+                    blow.hplus += launcher.hplus;
+                    blow.dplus += launcher.dplus;
+                } else if missile.launch < 0 {
+                    blow.damage = &missile.hurl_dmg;
+                }
+                blow
+            }
+        }
+    }
+}
+
+/// Roll several attacks (Rogue's `roll_em`). The damage string may hold
+/// several attacks, like `"1x8/1x8/2x6"`; each one swings separately.
+/// Sleeping or held defenders are hit more easily. Returns true if any
+/// attack hit.
+pub fn roll_em<R: Rng + ?Sized>(
+    att: &Stats,
+    def: &mut Stats,
+    def_arm: i32,
+    def_running: bool,
+    blow: Blow,
+    rng: &mut R,
+) -> bool {
+    let mut hplus = blow.hplus;
+    if !def_running {
+        hplus += 4;
+    }
+
+    let mut did_hit = false;
+    for attack in blow.damage.split('/') {
+        let Some((ndice, nsides)) = attack.split_once('x') else {
+            break;
+        };
+        let ndice = ndice.trim().parse().unwrap_or(0);
+        let nsides = nsides.trim().parse().unwrap_or(0);
+        if swing(att.level, def_arm, hplus + str_plus(att.strength), rng) {
+            let proll = roll(rng, ndice, nsides);
+            let damage = blow.dplus + proll + add_dam(att.strength);
+            def.hp -= damage.max(0);
+            did_hit = true;
+        }
+    }
+    did_hit
+}
+
+/// How the hero's attack on a monster went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FightOutcome {
+    Missed,
+    Hit,
+    /// The blow killed the monster.
+    Killed,
+    /// The "object" turned out to be a xeroc, and the hero stopped short.
+    Revealed,
+}
+
+/// How a monster's attack on the hero went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackOutcome {
+    Missed,
+    Hit,
+    /// The hero died; `killer` is the monster's letter, for `killname`.
+    Killed {
+        killer: char,
+    },
+}
+
+/// Name of a monster as the hero sees it (Rogue's `set_mname`).
+pub fn set_mname(level: &DungeonLevel, hero: &MonsterThing, tp: &MonsterThing) -> String {
+    if see_monst(level, hero, tp) {
+        format!("the {}", monster_info(tp.kind).name)
+    } else {
+        String::from("it")
+    }
+}
+
+/// What killed the hero, for the tombstone (Rogue's `killname`).
+pub fn killname(monst: char, doart: bool) -> String {
+    let (name, article) = match monst {
+        'a' => ("arrow", true),
+        'b' => ("bolt", true),
+        'd' => ("dart", true),
+        'h' => ("hypothermia", false),
+        's' => ("starvation", false),
+        'A'..='Z' => (monster_info(monst).name, true),
+        _ => ("Wally the Wonder Badger", false),
+    };
+    if doart && article {
+        let a = if name.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            "an"
+        } else {
+            "a"
+        };
+        format!("{} {}", a, name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Capitalize the first letter of a message.
+fn upper_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Message for a hit, `er` hitting `ee`; `None` is the hero (Rogue's `hit`).
+pub fn hit_msg<R: Rng + ?Sized>(er: Option<&str>, ee: Option<&str>, rng: &mut R) -> String {
+    const HERO: [&str; 4] = [
+        "scored an excellent hit on",
+        "hit",
+        "have injured",
+        "swing and hit",
+    ];
+    const MONSTER: [&str; 4] = [
+        "scored an excellent hit on",
+        "hits",
+        "has injured",
+        "swings and hits",
+    ];
+    let i = rnd(rng, 4) as usize;
+    let verb = if er.is_some() { MONSTER[i] } else { HERO[i] };
+    upper_first(&format!(
+        "{} {} {}",
+        er.unwrap_or("you"),
+        verb,
+        ee.unwrap_or("you")
+    ))
+}
+
+/// Message for a miss, `er` missing `ee`; `None` is the hero (Rogue's `miss`).
+pub fn miss_msg<R: Rng + ?Sized>(er: Option<&str>, ee: Option<&str>, rng: &mut R) -> String {
+    const HERO: [&str; 4] = ["miss", "swing and miss", "barely miss", "don't hit"];
+    const MONSTER: [&str; 4] = [
+        "misses",
+        "swings and misses",
+        "barely misses",
+        "doesn't hit",
+    ];
+    let i = rnd(rng, 4) as usize;
+    let verb = if er.is_some() { MONSTER[i] } else { HERO[i] };
+    upper_first(&format!(
+        "{} {} {}",
+        er.unwrap_or("you"),
+        verb,
+        ee.unwrap_or("you")
+    ))
+}
+
+/// What a thrown object is called in fight messages, or `None` if the
+/// hero gets the credit. A bolt from a stick carries its name as its label.
+fn missile_name(missile: &ObjectThing) -> Option<String> {
+    if missile.kind != WEAPON {
+        return None;
+    }
+    let name = WEAP_INFO
+        .get(missile.which as usize)
+        .map(|i| i.name)
+        .or(missile.label.as_deref())
+        .unwrap_or("bolt");
+    Some(format!("the {}", name))
+}
+
+/// The hero attacks the monster at index `idx` (Rogue's `fight`), which
+/// starts it chasing the hero.
+pub fn fight<R: Rng + ?Sized>(
+    dungeon: &mut Dungeon,
+    hero: &mut MonsterThing,
+    idx: usize,
+    weapon: Weapon,
+    rings: RingBonus,
+    messages: &mut Vec<String>,
+    rng: &mut R,
+) -> FightOutcome {
+    let thrown = matches!(weapon, Weapon::Thrown { .. });
+    let level = &mut dungeon.level;
+    let pos = level.monsters[idx].pos;
+    runto(level, pos, hero, rng);
+
+    // Let him know it was really a xeroc (if it was).
+    let tp = &mut level.monsters[idx];
+    if tp.kind == 'X' && tp.disguise != 'X' && !hero.on(IS_BLIND) {
+        tp.disguise = 'X';
+        messages.push(String::from("Wait!  That's a xeroc!"));
+        if !thrown {
+            return FightOutcome::Revealed;
+        }
+    }
+
+    let mname = set_mname(level, hero, &level.monsters[idx]);
+    let tp = &mut level.monsters[idx];
+    let def_running = tp.on(IS_RUN);
+    let def_arm = tp.stats.armor;
+    let mut blow = Blow::new(&hero.stats, weapon);
+    blow.hplus += rings.hplus;
+    blow.dplus += rings.dplus;
+    if roll_em(&hero.stats, &mut tp.stats, def_arm, def_running, blow, rng) {
+        let msg = match weapon {
+            Weapon::Thrown { missile, .. } => match missile_name(missile) {
+                Some(name) => format!("{} hits {}", upper_first(&name), mname),
+                None => format!("You hit {}", mname),
+            },
+            _ => hit_msg(None, Some(&mname), rng),
+        };
+        messages.push(msg);
+        // Glowing hands from a scroll of monster confusion.
+        if hero.on(CAN_HUH) {
+            level.monsters[idx].flags |= IS_HUH;
+            hero.flags &= !CAN_HUH;
+            let color = pick_color("red", hero.on(IS_HALU), rng);
+            messages.push(format!("Your hands stop glowing {}", color));
+        }
+        if level.monsters[idx].stats.hp <= 0 {
+            killed(dungeon, hero, idx, true, messages, rng);
+            return FightOutcome::Killed;
+        }
+        FightOutcome::Hit
+    } else {
+        let msg = match weapon {
+            Weapon::Thrown { missile, .. } => match missile_name(missile) {
+                Some(name) => format!("{} misses {}", upper_first(&name), mname),
+                None => format!("You missed {}", mname),
+            },
+            _ => miss_msg(None, Some(&mname), rng),
+        };
+        messages.push(msg);
+        FightOutcome::Missed
+    }
+}
+
+/// The monster at index `idx` attacks the hero (Rogue's `attack`).
+///
+/// `hero_arm` is the hero's armor class with armor and rings counted.
+/// Of the special attacks only the venus flytrap's grip is done here;
+/// others, such as rusting armor or stealing gold, are not.
+pub fn attack<R: Rng + ?Sized>(
+    dungeon: &mut Dungeon,
+    hero: &mut MonsterThing,
+    hero_arm: i32,
+    idx: usize,
+    messages: &mut Vec<String>,
+    rng: &mut R,
+) -> AttackOutcome {
+    let level = &mut dungeon.level;
+    let tp = &mut level.monsters[idx];
+    // Since this is an attack, stop it from hiding.
+    if tp.kind == 'X' && tp.disguise != 'X' && !hero.on(IS_BLIND) {
+        tp.disguise = 'X';
+    }
+
+    let mname = set_mname(level, hero, &level.monsters[idx]);
+    let tp = &mut level.monsters[idx];
+    let blow = Blow::new(&tp.stats, Weapon::Hands);
+    if roll_em(&tp.stats, &mut hero.stats, hero_arm, true, blow, rng) {
+        if tp.kind != 'I' {
+            messages.push(hit_msg(Some(&mname), None, rng));
+        }
+        if tp.kind == 'F' && !tp.on(IS_CANC) {
+            // A venus flytrap holds on and squeezes harder every time; its
+            // damage dice count its hits (Rogue's `fung_hit`).
+            hero.flags |= IS_HELD;
+            let dice = tp.stats.damage.split('x').next();
+            let hits: i32 = dice.and_then(|n| n.parse().ok()).unwrap_or(0);
+            tp.stats.damage = format!("{}x1", hits + 1);
+            hero.stats.hp -= 1;
+        }
+        if hero.stats.hp <= 0 {
+            return AttackOutcome::Killed { killer: tp.kind };
+        }
+        AttackOutcome::Hit
+    } else {
+        if tp.kind != 'I' {
+            messages.push(miss_msg(Some(&mname), None, rng));
+        }
+        AttackOutcome::Missed
+    }
+}
+
+/// Pick a random floor or passage spot around `pos` that the hero is not
+/// standing on (Rogue's `fallpos`).
+pub fn fallpos<R: Rng + ?Sized>(
+    level: &DungeonLevel,
+    pos: IVec2,
+    hero_pos: IVec2,
+    rng: &mut R,
+) -> Option<IVec2> {
+    let mut cnt = 0;
+    let mut newpos = None;
+    for y in pos.y - 1..=pos.y + 1 {
+        for x in pos.x - 1..=pos.x + 1 {
+            let spot = IVec2::new(x, y);
+            if spot == hero_pos {
+                continue;
+            }
+            let class = level.cell_class_at(spot);
+            if matches!(
+                class,
+                Some(TerrainCellClass::Floor | TerrainCellClass::Passage)
+            ) {
+                cnt += 1;
+                if rnd(rng, cnt) == 0 {
+                    newpos = Some(spot);
+                }
+            }
+        }
+    }
+    newpos
+}
+
+/// Drop an object someplace around its position (Rogue's `fall`). Returns
+/// false if there was no room and it vanished.
+pub fn fall<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    mut obj: ObjectThing,
+    hero_pos: IVec2,
+    rng: &mut R,
+) -> bool {
+    let Some(fpos) = fallpos(level, obj.pos, hero_pos, rng) else {
+        return false;
+    };
+    if let Some(cell) = level.terrain.get_mut(fpos.x as usize, fpos.y as usize) {
+        cell.cell_class = object_cell_class(obj.kind);
+    }
+    obj.pos = fpos;
+    level.objects.push(obj);
+    true
+}
+
+/// Put the monster at index `idx` to death (Rogue's `killed`): give the
+/// hero its experience and drop what it carried. With `pr` set, say so.
+/// The caller checks for a new level with `Player::check_level`.
+pub fn killed<R: Rng + ?Sized>(
+    dungeon: &mut Dungeon,
+    hero: &mut MonsterThing,
+    idx: usize,
+    pr: bool,
+    messages: &mut Vec<String>,
+    rng: &mut R,
+) {
+    let mname = set_mname(&dungeon.level, hero, &dungeon.level.monsters[idx]);
+    let mut tp = dungeon.level.monsters.remove(idx);
+    hero.stats.exp += tp.stats.exp;
+
+    match tp.kind {
+        // If the monster was a venus flytrap, un-hold him.
+        'F' => hero.flags &= !IS_HELD,
+        // Leprechauns leave their gold behind, but only on new levels.
+        'L' if dungeon.depth >= dungeon.max_depth => {
+            let mut gold = ObjectThing::new(GOLD, 0);
+            gold.armor = goldcalc(dungeon.depth, rng);
+            if save_throw(VS_MAGIC, &hero.stats, rng) {
+                for _ in 0..4 {
+                    gold.armor += goldcalc(dungeon.depth, rng);
+                }
+            }
+            tp.pack.push(Thing::Object(gold));
+        }
+        _ => {}
+    }
+
+    for thing in tp.pack.drain(..) {
+        if let Thing::Object(mut obj) = thing {
+            obj.pos = tp.pos;
+            fall(&mut dungeon.level, obj, hero.pos, rng);
+        }
+    }
+
+    if pr {
+        messages.push(format!("You have defeated {}", mname));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon_level::level_parser::parse_dungeon_level;
+    use crate::game::player::Player;
+    use crate::rng::GameRng;
+    use crate::terminal::parse_terrain::MapLegend;
+    use crate::thing::new_thing::{init_weapon, ThingCounters};
+    use crate::thing::object_table::{ARROW, BOW};
+
+    /// A lit room with the hero at its left end and a monster of `kind`
+    /// next to the hero.
+    fn setup(kind: char, rng: &mut GameRng) -> (Dungeon, MonsterThing) {
+        let map = ["------", &format!("|@{}..|", kind), "------"];
+        let parsed = parse_dungeon_level(map, &MapLegend::default(), 1, rng).unwrap();
+        let dungeon = Dungeon {
+            depth: 1,
+            max_depth: 1,
+            level: parsed.level,
+        };
+        let mut hero = Player::new(parsed.player.unwrap()).creature;
+        hero.room_id = Some(0);
+        (dungeon, hero)
+    }
+
+    #[test]
+    fn swing_needs_a_high_enough_roll() {
+        let mut rng = GameRng::new(1);
+        for _ in 0..100 {
+            assert!(swing(1, 10, 20, &mut rng));
+            assert!(!swing(1, 10, -20, &mut rng));
+        }
+        let hits = (0..1000).filter(|_| swing(1, 10, 0, &mut rng)).count();
+        // Rolls of 9 to 19 hit: 11 in 20.
+        assert!((450..650).contains(&hits), "{} hits", hits);
+    }
+
+    #[test]
+    fn roll_em_swings_once_per_attack() {
+        let mut rng = GameRng::new(4);
+        let att = Stats::new(16, 0, 1, 10, 12, "1x1/1x1");
+        let mut def = Stats::new(10, 0, 1, 10, 20, "1x1");
+        let blow = Blow {
+            hplus: 100,
+            ..Blow::new(&att, Weapon::Hands)
+        };
+        assert!(roll_em(&att, &mut def, 10, true, blow, &mut rng));
+        // Two attacks of 1 point each, plus 1 each for strength 16.
+        assert_eq!(def.hp, 16);
+
+        let blow = Blow {
+            hplus: -100,
+            ..Blow::new(&att, Weapon::Hands)
+        };
+        assert!(!roll_em(&att, &mut def, 10, true, blow, &mut rng));
+        assert_eq!(def.hp, 16);
+    }
+
+    #[test]
+    fn arrows_fired_from_a_bow_use_both_bonuses() {
+        let mut rng = GameRng::new(2);
+        let mut counters = ThingCounters::default();
+        let mut bow = init_weapon(BOW, &mut rng, &mut counters);
+        bow.hplus = 1;
+        bow.dplus = 2;
+        let arrow = init_weapon(ARROW, &mut rng, &mut counters);
+        let stats = Stats::new(16, 0, 1, 10, 12, "1x4");
+
+        let fired = Blow::new(
+            &stats,
+            Weapon::Thrown {
+                missile: &arrow,
+                wielded: Some(&bow),
+            },
+        );
+        assert_eq!(fired.damage, arrow.hurl_dmg);
+        assert_eq!((fired.hplus, fired.dplus), (1, 2));
+
+        let thrown = Blow::new(
+            &stats,
+            Weapon::Thrown {
+                missile: &arrow,
+                wielded: None,
+            },
+        );
+        assert_eq!(thrown.damage, arrow.damage);
+        assert_eq!((thrown.hplus, thrown.dplus), (0, 0));
+    }
+
+    #[test]
+    fn killing_a_monster_gives_its_experience() {
+        let mut rng = GameRng::new(6);
+        let (mut dungeon, mut hero) = setup('K', &mut rng);
+        dungeon.level.monsters[0].stats.hp = 1;
+        let exp = dungeon.level.monsters[0].stats.exp;
+        let rings = RingBonus {
+            hplus: 100,
+            dplus: 0,
+        };
+        let mut messages = Vec::new();
+        let outcome = fight(
+            &mut dungeon,
+            &mut hero,
+            0,
+            Weapon::Hands,
+            rings,
+            &mut messages,
+            &mut rng,
+        );
+        assert_eq!(outcome, FightOutcome::Killed);
+        assert_eq!(hero.stats.exp, exp);
+        assert!(dungeon.level.monsters.is_empty());
+        assert_eq!(
+            messages.last().map(String::as_str),
+            Some("You have defeated the kestrel")
+        );
+    }
+
+    #[test]
+    fn a_flytrap_holds_on_and_squeezes_harder() {
+        let mut rng = GameRng::new(8);
+        let (mut dungeon, mut hero) = setup('F', &mut rng);
+        hero.stats.hp = 50;
+        let mut messages = Vec::new();
+        let outcome = attack(&mut dungeon, &mut hero, 100, 0, &mut messages, &mut rng);
+        assert_eq!(outcome, AttackOutcome::Hit);
+        assert!(hero.on(IS_HELD));
+        assert_eq!(hero.stats.hp, 49);
+        assert_eq!(dungeon.level.monsters[0].stats.damage, "1x1");
+
+        let mut rng = GameRng::new(8);
+        let (mut dungeon, mut hero) = setup('F', &mut rng);
+        dungeon.level.monsters[0].flags |= IS_CANC;
+        attack(&mut dungeon, &mut hero, 100, 0, &mut messages, &mut rng);
+        assert!(!hero.on(IS_HELD));
+    }
+}
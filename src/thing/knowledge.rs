@@ -0,0 +1,415 @@
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::thing::object_table::{
+    info_table, MAX_POTIONS, MAX_RINGS, MAX_SCROLLS, MAX_STICKS, POT_INFO, RING_INFO, SCR_INFO,
+    WS_INFO,
+};
+use crate::thing::{POTION, RING, SCROLL, STICK};
+
+/* Longest scroll title, in characters. */
+pub const MAXNAME: usize = 40;
+
+/// Colors potions come in (Rogue's `rainbow`).
+pub const RAINBOW: [&str; 27] = [
+    "amber",
+    "aquamarine",
+    "black",
+    "blue",
+    "brown",
+    "clear",
+    "crimson",
+    "cyan",
+    "ecru",
+    "gold",
+    "green",
+    "grey",
+    "magenta",
+    "orange",
+    "pink",
+    "plaid",
+    "purple",
+    "red",
+    "silver",
+    "tan",
+    "tangerine",
+    "topaz",
+    "turquoise",
+    "vermilion",
+    "violet",
+    "white",
+    "yellow",
+];
+
+/// A color for a message: the real one, or any at all while hallucinating
+/// (Rogue's `pick_color`).
+pub fn pick_color<'a, R: Rng + ?Sized>(col: &'a str, halu: bool, rng: &mut R) -> &'a str {
+    if halu {
+        RAINBOW[rnd(rng, RAINBOW.len() as i32) as usize]
+    } else {
+        col
+    }
+}
+
+/// Syllables scroll titles are made of (Rogue's `sylls`).
+pub const SYLLS: [&str; 147] = [
+    "a", "ab", "ag", "aks", "ala", "an", "app", "arg", "arze", "ash", "bek", "bie", "bit", "bjor",
+    "blu", "bot", "bu", "byt", "comp", "con", "cos", "cre", "dalf", "dan", "den", "do", "e", "eep",
+    "el", "eng", "er", "ere", "erk", "esh", "evs", "fa", "fid", "fri", "fu", "gan", "gar", "glen",
+    "gop", "gre", "ha", "hyd", "i", "ing", "ip", "ish", "it", "ite", "iv", "jo", "kho", "kli",
+    "klis", "la", "lech", "mar", "me", "mi", "mic", "mik", "mon", "mung", "mur", "nej", "nelg",
+    "nep", "ner", "nes", "nes", "nih", "nin", "o", "od", "ood", "org", "orn", "ox", "oxy", "pay",
+    "ple", "plu", "po", "pot", "prok", "re", "rea", "rhov", "ri", "ro", "rog", "rok", "rol", "sa",
+    "san", "sat", "sef", "seh", "shu", "ski", "sna", "sne", "snik", "sno", "so", "sol", "sri",
+    "sta", "sun", "ta", "tab", "tem", "ther", "ti", "tox", "trol", "tue", "turs", "u", "ulk", "um",
+    "un", "uni", "ur", "val", "viv", "vly", "vom", "wah", "wed", "werg", "wex", "whon", "wun",
+    "xo", "y", "yot", "yu", "zant", "zeb", "zim", "zok", "zon", "zum",
+];
+
+/// Stones rings are set with, and what each adds to a ring's worth
+/// (Rogue's `stones`).
+pub const STONES: [(&str, i32); 26] = [
+    ("agate", 25),
+    ("alexandrite", 40),
+    ("amethyst", 50),
+    ("carnelian", 40),
+    ("diamond", 300),
+    ("emerald", 300),
+    ("germanium", 225),
+    ("granite", 5),
+    ("garnet", 50),
+    ("jade", 150),
+    ("kryptonite", 300),
+    ("lapis lazuli", 50),
+    ("moonstone", 50),
+    ("obsidian", 15),
+    ("onyx", 60),
+    ("opal", 200),
+    ("pearl", 220),
+    ("peridot", 63),
+    ("ruby", 350),
+    ("sapphire", 285),
+    ("stibotantalite", 200),
+    ("tiger eye", 50),
+    ("topaz", 60),
+    ("turquoise", 70),
+    ("taaffeite", 300),
+    ("zircon", 80),
+];
+
+/// Woods staffs are made of (Rogue's `wood`).
+pub const WOOD: [&str; 33] = [
+    "avocado wood",
+    "balsa",
+    "bamboo",
+    "banyan",
+    "birch",
+    "cedar",
+    "cherry",
+    "cinnibar",
+    "cypress",
+    "dogwood",
+    "driftwood",
+    "ebony",
+    "elm",
+    "eucalyptus",
+    "fall",
+    "hemlock",
+    "holly",
+    "ironwood",
+    "kukui wood",
+    "mahogany",
+    "manzanita",
+    "maple",
+    "oaken",
+    "persimmon wood",
+    "pecan",
+    "pine",
+    "poplar",
+    "redwood",
+    "rosewood",
+    "spruce",
+    "teak",
+    "walnut",
+    "zebrawood",
+];
+
+/// Metals wands are made of (Rogue's `metal`).
+pub const METAL: [&str; 22] = [
+    "aluminum",
+    "beryllium",
+    "bone",
+    "brass",
+    "bronze",
+    "copper",
+    "electrum",
+    "gold",
+    "iron",
+    "lead",
+    "magnesium",
+    "mercury",
+    "nickel",
+    "pewter",
+    "platinum",
+    "steel",
+    "silver",
+    "silicon",
+    "tin",
+    "titanium",
+    "tungsten",
+    "zinc",
+];
+
+/// What the hero knows about one type of object (Rogue's `oi_know` and
+/// `oi_guess`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeKnowledge {
+    /// The type has been identified.
+    pub know: bool,
+    /// What the player has called the type.
+    pub guess: Option<String>,
+}
+
+/// How unidentified things look this game, and what the hero has learned
+/// about them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Knowledge {
+    /// Potion colors (Rogue's `p_colors`).
+    pub p_colors: Vec<String>,
+    /// Scroll titles (Rogue's `s_names`).
+    pub s_names: Vec<String>,
+    /// Ring stones (Rogue's `r_stones`).
+    pub r_stones: Vec<String>,
+    /// What each ring's stone adds to its worth.
+    pub r_worth: Vec<i32>,
+    /// Stick materials (Rogue's `ws_made`).
+    pub ws_made: Vec<String>,
+    /// Whether each stick is a "wand" or a "staff" (Rogue's `ws_type`).
+    pub ws_type: Vec<String>,
+    pub potions: Vec<TypeKnowledge>,
+    pub scrolls: Vec<TypeKnowledge>,
+    pub rings: Vec<TypeKnowledge>,
+    pub sticks: Vec<TypeKnowledge>,
+}
+
+/// Pick an entry of a list nobody has used yet.
+fn pick_unused<R: Rng + ?Sized>(used: &mut [bool], rng: &mut R) -> usize {
+    loop {
+        let j = rnd(rng, used.len() as i32) as usize;
+        if !used[j] {
+            used[j] = true;
+            return j;
+        }
+    }
+}
+
+/// Make up a scroll title from random syllables.
+fn scroll_title<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let mut title = String::new();
+    for _ in 0..rnd(rng, 3) + 2 {
+        for _ in 0..rnd(rng, 3) + 1 {
+            let sp = SYLLS[rnd(rng, SYLLS.len() as i32) as usize];
+            if title.len() + sp.len() > MAXNAME {
+                break;
+            }
+            title.push_str(sp);
+        }
+        title.push(' ');
+    }
+    title.pop();
+    title
+}
+
+impl Knowledge {
+    /// Shuffle the appearances for a new game, knowing nothing yet (Rogue's
+    /// `init_colors`, `init_names`, `init_stones` and `init_materials`).
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut used = [false; RAINBOW.len()];
+        let p_colors = (0..MAX_POTIONS)
+            .map(|_| RAINBOW[pick_unused(&mut used, rng)].to_string())
+            .collect();
+
+        let s_names = (0..MAX_SCROLLS).map(|_| scroll_title(rng)).collect();
+
+        let mut used = [false; STONES.len()];
+        let (r_stones, r_worth) = (0..MAX_RINGS)
+            .map(|_| {
+                let (stone, worth) = STONES[pick_unused(&mut used, rng)];
+                (stone.to_string(), worth)
+            })
+            .unzip();
+
+        let mut used_wood = [false; WOOD.len()];
+        let mut used_metal = [false; METAL.len()];
+        let (ws_made, ws_type) = (0..MAX_STICKS)
+            .map(|_| loop {
+                if rnd(rng, 2) == 0 {
+                    let j = rnd(rng, METAL.len() as i32) as usize;
+                    if !used_metal[j] {
+                        used_metal[j] = true;
+                        break (METAL[j].to_string(), String::from("wand"));
+                    }
+                } else {
+                    let j = rnd(rng, WOOD.len() as i32) as usize;
+                    if !used_wood[j] {
+                        used_wood[j] = true;
+                        break (WOOD[j].to_string(), String::from("staff"));
+                    }
+                }
+            })
+            .unzip();
+
+        Knowledge {
+            p_colors,
+            s_names,
+            r_stones,
+            r_worth,
+            ws_made,
+            ws_type,
+            potions: vec![TypeKnowledge::default(); MAX_POTIONS],
+            scrolls: vec![TypeKnowledge::default(); MAX_SCROLLS],
+            rings: vec![TypeKnowledge::default(); MAX_RINGS],
+            sticks: vec![TypeKnowledge::default(); MAX_STICKS],
+        }
+    }
+
+    /// The knowledge entries for a type of object, for the types that hide
+    /// what they are.
+    pub fn table(&self, kind: i32) -> Option<&[TypeKnowledge]> {
+        match kind {
+            POTION => Some(&self.potions),
+            SCROLL => Some(&self.scrolls),
+            RING => Some(&self.rings),
+            STICK => Some(&self.sticks),
+            _ => None,
+        }
+    }
+
+    fn table_mut(&mut self, kind: i32) -> Option<&mut Vec<TypeKnowledge>> {
+        match kind {
+            POTION => Some(&mut self.potions),
+            SCROLL => Some(&mut self.scrolls),
+            RING => Some(&mut self.rings),
+            STICK => Some(&mut self.sticks),
+            _ => None,
+        }
+    }
+
+    fn entry(&self, kind: i32, which: i32) -> Option<&TypeKnowledge> {
+        self.table(kind)?.get(usize::try_from(which).ok()?)
+    }
+
+    /// Whether the hero knows what this type of object is. Types that never
+    /// hide it, like weapons, always count as known.
+    pub fn is_known(&self, kind: i32, which: i32) -> bool {
+        match self.table(kind) {
+            Some(_) => self.entry(kind, which).is_some_and(|e| e.know),
+            None => true,
+        }
+    }
+
+    /// What the player has called a type, if it is not known.
+    pub fn guess(&self, kind: i32, which: i32) -> Option<&str> {
+        self.entry(kind, which)
+            .filter(|e| !e.know)
+            .and_then(|e| e.guess.as_deref())
+    }
+
+    /// Learn what a type of object is; any name it was called is dropped.
+    pub fn identify(&mut self, kind: i32, which: i32) {
+        if let Some(e) = self
+            .table_mut(kind)
+            .and_then(|t| t.get_mut(usize::try_from(which).ok()?))
+        {
+            e.know = true;
+            e.guess = None;
+        }
+    }
+
+    /// Call a type of object something, or forget the name with `None`
+    /// (Rogue's `call`). Known types cannot be called.
+    pub fn call(&mut self, kind: i32, which: i32, name: Option<String>) -> bool {
+        match self
+            .table_mut(kind)
+            .and_then(|t| t.get_mut(usize::try_from(which).ok()?))
+        {
+            Some(e) if !e.know => {
+                e.guess = name.filter(|n| !n.is_empty());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// How an unidentified object of this type looks: a potion's color,
+    /// a scroll's title, a ring's stone or a stick's material.
+    pub fn appearance(&self, kind: i32, which: i32) -> Option<&str> {
+        let which = usize::try_from(which).ok()?;
+        let names = match kind {
+            POTION => &self.p_colors,
+            SCROLL => &self.s_names,
+            RING => &self.r_stones,
+            STICK => &self.ws_made,
+            _ => return None,
+        };
+        names.get(which).map(String::as_str)
+    }
+
+    /// Word for a type of object: "wand" or "staff" for sticks.
+    pub fn type_name(&self, kind: i32, which: i32) -> &str {
+        match kind {
+            POTION => "potion",
+            SCROLL => "scroll",
+            RING => "ring",
+            STICK => usize::try_from(which)
+                .ok()
+                .and_then(|w| self.ws_type.get(w))
+                .map_or("stick", String::as_str),
+            _ => "thing",
+        }
+    }
+
+    /// What an object of this type is worth, counting a ring's stone.
+    pub fn worth(&self, kind: i32, which: i32) -> i32 {
+        let Some(base) = info_table(kind).and_then(|t| t.get(which as usize)) else {
+            return 0;
+        };
+        match kind {
+            RING => base.worth + self.r_worth.get(which as usize).copied().unwrap_or(0),
+            _ => base.worth,
+        }
+    }
+
+    /// Lines listing what has been found out about one type of object, or
+    /// all of them (Rogue's `discovered`): identified types by name,
+    /// called ones by what they were called.
+    pub fn discoveries(&self, kind: Option<i32>) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (k, info) in [
+            (POTION, &POT_INFO[..]),
+            (SCROLL, &SCR_INFO[..]),
+            (RING, &RING_INFO[..]),
+            (STICK, &WS_INFO[..]),
+        ] {
+            if kind.is_some_and(|want| want != k) {
+                continue;
+            }
+            for (which, entry) in self.table(k).unwrap_or(&[]).iter().enumerate() {
+                let which = which as i32;
+                let what = self.type_name(k, which);
+                let looks = self.appearance(k, which).unwrap_or("");
+                if entry.know {
+                    lines.push(format!(
+                        "{} of {} ({})",
+                        what, info[which as usize].name, looks
+                    ));
+                } else if let Some(guess) = &entry.guess {
+                    lines.push(format!("{} called {} ({})", what, guess, looks));
+                }
+            }
+        }
+        lines
+    }
+}
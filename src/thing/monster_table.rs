@@ -0,0 +1,274 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::{rnd, roll};
+use crate::dungeon_level::dungeon_level::{DungeonLevel, AMULET_LEVEL};
+use crate::terminal::terminal_symbol::TerminalSymbol;
+use crate::thing::chase::runto;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::new_thing::{new_thing, ThingCounters};
+use crate::thing::stats::Stats;
+use crate::thing::{
+    MonsterThing, Thing, AMULET, ARMOR, FOOD, GOLD, IS_FLY, IS_GREED, IS_HASTE, IS_INVIS, IS_MEAN,
+    IS_REGEN, POTION, RING, SCROLL, STICK, WEAPON,
+};
+
+/// Monster template definition (Rogue's `struct monster`).
+#[derive(Debug, Clone)]
+pub struct MonsterInfo {
+    pub name: &'static str,
+    /// Probability out of 100 of carrying something.
+    pub carry: i32,
+    /// Things about the monster.
+    pub flags: i32,
+    /// Base statistics; hit points are rolled from the level instead.
+    pub stats: MonsterStats,
+}
+
+/// The `const`-friendly part of `Stats` used by the monster table.
+#[derive(Debug, Clone, Copy)]
+pub struct MonsterStats {
+    pub strength: i32,
+    pub exp: i32,
+    pub level: i32,
+    pub armor: i32,
+    pub damage: &'static str,
+}
+
+const fn monster(
+    name: &'static str,
+    carry: i32,
+    flags: i32,
+    exp: i32,
+    level: i32,
+    armor: i32,
+    damage: &'static str,
+) -> MonsterInfo {
+    MonsterInfo {
+        name,
+        carry,
+        flags,
+        stats: MonsterStats {
+            strength: 10,
+            exp,
+            level,
+            armor,
+            damage,
+        },
+    }
+}
+
+/// The monster table, indexed by `kind - 'A'`.
+#[rustfmt::skip]
+pub const MONSTERS: [MonsterInfo; 26] = [
+    /*       Name            CARRY  FLAG                        exp  lvl  amr  dmg */
+    monster("aquator", 0, IS_MEAN, 20, 5, 2, "0x0/0x0"),
+    monster("bat", 0, IS_FLY, 1, 1, 3, "1x2"),
+    monster("centaur", 15, 0, 17, 4, 4, "1x2/1x5/1x5"),
+    monster("dragon", 100, IS_MEAN, 5000, 10, -1, "1x8/1x8/3x10"),
+    monster("emu", 0, IS_MEAN, 2, 1, 7, "1x2"),
+    // The flytrap's damage grows with each hit; see `attack`.
+    monster("venus flytrap", 0, IS_MEAN, 80, 8, 3, "0x0"),
+    monster("griffin", 20, IS_MEAN | IS_FLY | IS_REGEN, 2000, 13, 2, "4x3/3x5"),
+    monster("hobgoblin", 0, IS_MEAN, 3, 1, 5, "1x8"),
+    monster("ice monster", 0, 0, 5, 1, 9, "0x0"),
+    monster("jabberwock", 70, 0, 3000, 15, 6, "2x12/2x4"),
+    monster("kestrel", 0, IS_MEAN | IS_FLY, 1, 1, 7, "1x4"),
+    monster("leprechaun", 0, 0, 10, 3, 8, "1x1"),
+    monster("medusa", 40, IS_MEAN, 200, 8, 2, "3x4/3x4/2x5"),
+    monster("nymph", 100, 0, 37, 3, 9, "0x0"),
+    monster("orc", 15, IS_GREED, 5, 1, 6, "1x8"),
+    monster("phantom", 0, IS_INVIS, 120, 8, 3, "4x4"),
+    monster("quagga", 0, IS_MEAN, 15, 3, 3, "1x5/1x5"),
+    monster("rattlesnake", 0, IS_MEAN, 9, 2, 3, "1x6"),
+    monster("snake", 0, IS_MEAN, 2, 1, 5, "1x3"),
+    monster("troll", 50, IS_REGEN | IS_MEAN, 120, 6, 4, "1x8/1x8/2x6"),
+    monster("black unicorn", 0, IS_MEAN, 190, 7, -2, "1x9/1x9/2x9"),
+    monster("vampire", 20, IS_REGEN | IS_MEAN, 350, 8, 1, "1x10"),
+    monster("wraith", 0, 0, 55, 5, 4, "1x6"),
+    monster("xeroc", 30, 0, 100, 7, 7, "4x4"),
+    monster("yeti", 30, 0, 50, 4, 6, "1x6/1x6"),
+    monster("zombie", 0, IS_MEAN, 6, 2, 8, "1x8"),
+];
+
+/// Monsters in order of increasing toughness, used to pick a monster
+/// appropriate to the depth.
+#[rustfmt::skip]
+const LVL_MONS: [Option<char>; 26] = [
+    Some('K'), Some('E'), Some('B'), Some('S'), Some('H'), Some('I'), Some('R'),
+    Some('O'), Some('Z'), Some('L'), Some('C'), Some('Q'), Some('A'), Some('N'),
+    Some('Y'), Some('F'), Some('T'), Some('W'), Some('P'), Some('X'), Some('U'),
+    Some('M'), Some('V'), Some('G'), Some('J'), Some('D'),
+];
+
+/// Same as `LVL_MONS`, minus the monsters that never wander in.
+#[rustfmt::skip]
+const WAND_MONS: [Option<char>; 26] = [
+    Some('K'), Some('E'), Some('B'), Some('S'), Some('H'), None, Some('R'),
+    Some('O'), Some('Z'), None, Some('C'), Some('Q'), Some('A'), None,
+    Some('Y'), None, Some('T'), Some('W'), Some('P'), None, Some('U'),
+    Some('M'), Some('V'), Some('G'), Some('J'), None,
+];
+
+/// Look up the table entry for a monster letter.
+pub fn monster_info(kind: char) -> &'static MonsterInfo {
+    &MONSTERS[(kind as u8 - b'A') as usize]
+}
+
+/// Pick a monster to show up. The lower the depth, the meaner the monster
+/// (Rogue's `randmonster`).
+pub fn rand_monster<R: Rng + ?Sized>(depth: i32, wander: bool, rng: &mut R) -> char {
+    let mons = if wander { &WAND_MONS } else { &LVL_MONS };
+    loop {
+        let mut d = depth + (rnd(rng, 10) - 6);
+        if d < 0 {
+            d = rnd(rng, 5);
+        }
+        if d > 25 {
+            d = rnd(rng, 5) + 21;
+        }
+        if let Some(kind) = mons[d as usize] {
+            return kind;
+        }
+    }
+}
+
+/// Create a new monster of the given kind (Rogue's `new_monster`).
+///
+/// The caller is responsible for placing the monster on the level and
+/// setting its `room_id`.
+pub fn new_monster<R: Rng + ?Sized>(
+    kind: char,
+    pos: IVec2,
+    depth: i32,
+    rng: &mut R,
+) -> MonsterThing {
+    let lev_add = (depth - AMULET_LEVEL).max(0);
+    let mp = monster_info(kind);
+
+    let level = mp.stats.level + lev_add;
+    let max_hp = roll(rng, level, 8);
+    let mut stats = Stats {
+        strength: mp.stats.strength,
+        exp: mp.stats.exp + lev_add * 10,
+        level,
+        armor: mp.stats.armor - lev_add,
+        hp: max_hp,
+        damage: mp.stats.damage.to_string(),
+        max_hp,
+    };
+    stats.exp += exp_add(&stats);
+
+    let mut flags = mp.flags;
+    if depth > 29 {
+        flags |= IS_HASTE;
+    }
+
+    let disguise = if kind == 'X' {
+        rnd_thing(depth, rng)
+    } else {
+        kind
+    };
+
+    MonsterThing {
+        pos,
+        turn: true,
+        kind,
+        disguise,
+        old_ch: ' ',
+        dest: None,
+        flags,
+        stats,
+        room_id: None,
+        pack: Vec::new(),
+        reserved: 0,
+    }
+}
+
+/// Put a new monster on the level where it stands, noting its room and
+/// what it hides. Returns its index.
+pub fn put_monster(level: &mut DungeonLevel, mut tp: MonsterThing) -> usize {
+    tp.room_id = level.room_at(tp.pos);
+    tp.old_ch = level
+        .cell_class_at(tp.pos)
+        .map_or(' ', TerminalSymbol::char_from_terrain_cell_class);
+    level.monsters.push(tp);
+    level.monsters.len() - 1
+}
+
+/// Tries at finding a spot for a wandering monster outside the hero's room.
+const WANDER_TRIES: u32 = 100;
+
+/// Bring in a wandering monster somewhere outside the hero's room and set
+/// it running at the hero (Rogue's `wanderer`). Returns its index, or
+/// `None` if there was nowhere to put it.
+pub fn wanderer<R: Rng + ?Sized>(
+    level: &mut DungeonLevel,
+    depth: i32,
+    hero: &MonsterThing,
+    rng: &mut R,
+) -> Option<usize> {
+    let proom = level.room_at(hero.pos);
+    let pos = (0..WANDER_TRIES)
+        .filter_map(|_| level.find_floor(None, 1, true, rng))
+        .find(|&pos| level.room_at(pos) != proom)?;
+
+    let kind = rand_monster(depth, true, rng);
+    let idx = put_monster(level, new_monster(kind, pos, depth, rng));
+    runto(level, pos, hero, rng);
+    Some(idx)
+}
+
+/// Experience to add for this monster's level/hit points (Rogue's `exp_add`).
+pub fn exp_add(stats: &Stats) -> i32 {
+    let mut m = if stats.level == 1 {
+        stats.max_hp / 8
+    } else {
+        stats.max_hp / 6
+    };
+    if stats.level > 9 {
+        m *= 20;
+    } else if stats.level > 6 {
+        m *= 4;
+    }
+    m
+}
+
+/// Give a pack to a monster if it deserves one (Rogue's `give_pack`).
+///
+/// Rogue only does this on levels at or below the deepest one reached so
+/// far; callers check that before calling.
+pub fn give_pack<R: Rng + ?Sized>(
+    tp: &mut MonsterThing,
+    rng: &mut R,
+    counters: &mut ThingCounters,
+    knowledge: &Knowledge,
+) {
+    if rnd(rng, 100) < monster_info(tp.kind).carry {
+        tp.pack
+            .push(Thing::Object(new_thing(rng, counters, knowledge)));
+    }
+}
+
+/// Pick a random thing appropriate for this level, as shown by a mimic
+/// (Rogue's `rnd_thing`).
+pub fn rnd_thing<R: Rng + ?Sized>(depth: i32, rng: &mut R) -> char {
+    const THING_LIST: [i32; 10] = [
+        POTION,
+        SCROLL,
+        RING,
+        STICK,
+        FOOD,
+        WEAPON,
+        ARMOR,
+        TerminalSymbol::Stairs.as_char() as i32,
+        GOLD,
+        AMULET,
+    ];
+    let i = if depth >= AMULET_LEVEL {
+        rnd(rng, THING_LIST.len() as i32)
+    } else {
+        rnd(rng, THING_LIST.len() as i32 - 1)
+    };
+    char::from_u32(THING_LIST[i as usize] as u32).unwrap_or(' ')
+}
@@ -0,0 +1,287 @@
+use crate::thing::knowledge::Knowledge;
+use crate::thing::object_table::{
+    info_table, ARM_INFO, A_CLASS, R_ADDDAM, R_ADDHIT, R_ADDSTR, R_PROTECT, WEAP_INFO,
+};
+use crate::thing::{
+    ObjectThing, AMULET, ARMOR, FOOD, GOLD, IS_KNOW, POTION, RING, SCROLL, STICK, WEAPON,
+};
+
+/// How to word an object's name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NameStyle {
+    /// Leave out words a terse player does not want to read.
+    pub terse: bool,
+    /// Start in lowercase, for use in the middle of a sentence (Rogue's
+    /// `drop` argument).
+    pub lowercase: bool,
+}
+
+/// "n" if a word starts with a vowel, for "a"/"an" (Rogue's `vowelstr`).
+pub fn vowelstr(s: &str) -> &'static str {
+    match s.chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u' | 'A' | 'E' | 'I' | 'O' | 'U') => "n",
+        _ => "",
+    }
+}
+
+/// Plus or minus numbers for enchantments, like "+1,-2" (Rogue's `num`).
+pub fn num(n1: i32, n2: i32, kind: i32) -> String {
+    if kind == WEAPON {
+        format!("{:+},{:+}", n1, n2)
+    } else {
+        format!("{:+}", n1)
+    }
+}
+
+/// Bracketed enchantment of a known ring that has one (Rogue's `ring_num`).
+fn ring_num(obj: &ObjectThing) -> String {
+    if !obj.on(IS_KNOW) {
+        return String::new();
+    }
+    match obj.which {
+        R_PROTECT | R_ADDSTR | R_ADDDAM | R_ADDHIT => format!(" [{}]", num(obj.armor, 0, RING)),
+        _ => String::new(),
+    }
+}
+
+/// Bracketed charges of a known stick (Rogue's `charge_str`).
+fn charge_str(obj: &ObjectThing, terse: bool) -> String {
+    if !obj.on(IS_KNOW) {
+        String::new()
+    } else if terse {
+        format!(" [{}]", obj.armor)
+    } else {
+        format!(" [{} charges]", obj.armor)
+    }
+}
+
+/// Name a potion, ring or stick by what is known of its type (Rogue's
+/// `nameit`).
+fn nameit(obj: &ObjectThing, knowledge: &Knowledge, extra: &str) -> String {
+    let kind_name = knowledge.type_name(obj.kind, obj.which);
+    let looks = knowledge.appearance(obj.kind, obj.which).unwrap_or("");
+    let real = info_table(obj.kind)
+        .and_then(|t| t.get(obj.which as usize))
+        .map_or("", |i| i.name);
+
+    let known = knowledge.is_known(obj.kind, obj.which);
+    let guess = knowledge.guess(obj.kind, obj.which);
+    if known || guess.is_some() {
+        let mut name = if obj.count == 1 {
+            format!("A {} ", kind_name)
+        } else {
+            format!("{} {}s ", obj.count, kind_name)
+        };
+        match guess {
+            Some(guess) if !known => name += &format!("called {}{}({})", guess, extra, looks),
+            _ => name += &format!("of {}{}({})", real, extra, looks),
+        }
+        name
+    } else if obj.count == 1 {
+        format!("A{} {} {}", vowelstr(looks), looks, kind_name)
+    } else {
+        format!("{} {} {}s", obj.count, looks, kind_name)
+    }
+}
+
+/// Describe an object for the inventory or a message, like "3 scrolls
+/// titled 'zok fribble'" or "A +1,+2 mace" (Rogue's `inv_name`).
+///
+/// `fruit` is what the player calls the fruit. What the hero has in hand
+/// or on is added by the caller.
+pub fn inv_name(obj: &ObjectThing, knowledge: &Knowledge, fruit: &str, style: NameStyle) -> String {
+    let which = obj.which;
+    let mut name = match obj.kind {
+        POTION => nameit(obj, knowledge, ""),
+        RING => nameit(obj, knowledge, &ring_num(obj)),
+        STICK => nameit(obj, knowledge, &charge_str(obj, style.terse)),
+        SCROLL => {
+            let mut name = if obj.count == 1 {
+                String::from("A scroll ")
+            } else {
+                format!("{} scrolls ", obj.count)
+            };
+            let real = info_table(SCROLL)
+                .and_then(|t| t.get(which as usize))
+                .map_or("", |i| i.name);
+            if knowledge.is_known(SCROLL, which) {
+                name += &format!("of {}", real);
+            } else if let Some(guess) = knowledge.guess(SCROLL, which) {
+                name += &format!("called {}", guess);
+            } else {
+                let title = knowledge.appearance(SCROLL, which).unwrap_or("");
+                name += &format!("titled '{}'", title);
+            }
+            name
+        }
+        FOOD => {
+            if which == 1 {
+                if obj.count == 1 {
+                    format!("A{} {}", vowelstr(fruit), fruit)
+                } else {
+                    format!("{} {}s", obj.count, fruit)
+                }
+            } else if obj.count == 1 {
+                String::from("Some food")
+            } else {
+                format!("{} rations of food", obj.count)
+            }
+        }
+        WEAPON => {
+            let sp = WEAP_INFO.get(which as usize).map_or("weapon", |i| i.name);
+            let mut name = if obj.count > 1 {
+                format!("{} ", obj.count)
+            } else {
+                format!("A{} ", vowelstr(sp))
+            };
+            if obj.on(IS_KNOW) {
+                name += &format!("{} {}", num(obj.hplus, obj.dplus, WEAPON), sp);
+            } else {
+                name += sp;
+            }
+            if obj.count > 1 {
+                name.push('s');
+            }
+            if let Some(label) = &obj.label {
+                name += &format!(" called {}", label);
+            }
+            name
+        }
+        ARMOR => {
+            let sp = ARM_INFO.get(which as usize).map_or("armor", |i| i.name);
+            let mut name = if obj.on(IS_KNOW) {
+                let a_class = A_CLASS.get(which as usize).copied().unwrap_or(obj.armor);
+                format!(
+                    "{} {} [{}{}]",
+                    num(a_class - obj.armor, 0, ARMOR),
+                    sp,
+                    if style.terse { "" } else { "protection " },
+                    10 - obj.armor
+                )
+            } else {
+                sp.to_string()
+            };
+            if let Some(label) = &obj.label {
+                name += &format!(" called {}", label);
+            }
+            name
+        }
+        AMULET => String::from("The Amulet of Yendor"),
+        GOLD => format!("{} Gold pieces", obj.armor),
+        _ => String::from("Something strange"),
+    };
+
+    // Capitalize or not, depending on where the name goes.
+    let first = name.chars().next();
+    if let Some(first) = first {
+        let changed: String = if style.lowercase {
+            first.to_lowercase().collect()
+        } else {
+            first.to_uppercase().collect()
+        };
+        name.replace_range(..first.len_utf8(), &changed);
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+    use crate::thing::object_table::{MACE, P_HEALING, RING_MAIL, S_MAP, WS_LIGHT};
+
+    /// Knowledge with fixed looks for the things named below.
+    fn knowledge() -> Knowledge {
+        let mut knowledge = Knowledge::new(&mut GameRng::new(1));
+        knowledge.s_names[S_MAP as usize] = String::from("zok fribble");
+        knowledge.p_colors[P_HEALING as usize] = String::from("amber");
+        knowledge.r_stones[R_ADDSTR as usize] = String::from("onyx");
+        knowledge.ws_made[WS_LIGHT as usize] = String::from("oak");
+        knowledge.ws_type[WS_LIGHT as usize] = String::from("staff");
+        knowledge
+    }
+
+    fn name(obj: &ObjectThing, knowledge: &Knowledge) -> String {
+        inv_name(obj, knowledge, "slime-mold", NameStyle::default())
+    }
+
+    fn many(kind: i32, which: i32, count: i32) -> ObjectThing {
+        let mut obj = ObjectThing::new(kind, which);
+        obj.count = count;
+        obj
+    }
+
+    #[test]
+    fn scrolls_by_title_guess_and_name() {
+        let mut knowledge = knowledge();
+        let scrolls = many(SCROLL, S_MAP, 3);
+        assert_eq!(name(&scrolls, &knowledge), "3 scrolls titled 'zok fribble'");
+        assert_eq!(
+            name(&many(SCROLL, S_MAP, 1), &knowledge),
+            "A scroll titled 'zok fribble'"
+        );
+        knowledge.call(SCROLL, S_MAP, Some(String::from("maps?")));
+        assert_eq!(name(&scrolls, &knowledge), "3 scrolls called maps?");
+        knowledge.identify(SCROLL, S_MAP);
+        assert_eq!(name(&scrolls, &knowledge), "3 scrolls of magic mapping");
+    }
+
+    #[test]
+    fn potions_rings_and_sticks_show_their_looks() {
+        let mut knowledge = knowledge();
+        let potion = many(POTION, P_HEALING, 1);
+        assert_eq!(name(&potion, &knowledge), "An amber potion");
+        assert_eq!(
+            name(&many(POTION, P_HEALING, 2), &knowledge),
+            "2 amber potions"
+        );
+        knowledge.identify(POTION, P_HEALING);
+        assert_eq!(name(&potion, &knowledge), "A potion of healing(amber)");
+
+        let mut ring = many(RING, R_ADDSTR, 1);
+        ring.armor = 2;
+        assert_eq!(name(&ring, &knowledge), "An onyx ring");
+        ring.flags |= IS_KNOW;
+        knowledge.identify(RING, R_ADDSTR);
+        assert_eq!(name(&ring, &knowledge), "A ring of add strength [+2](onyx)");
+
+        let mut staff = many(STICK, WS_LIGHT, 1);
+        staff.armor = 12;
+        assert_eq!(name(&staff, &knowledge), "An oak staff");
+        staff.flags |= IS_KNOW;
+        knowledge.identify(STICK, WS_LIGHT);
+        assert_eq!(
+            name(&staff, &knowledge),
+            "A staff of light [12 charges](oak)"
+        );
+        let terse = NameStyle {
+            terse: true,
+            lowercase: true,
+        };
+        assert_eq!(
+            inv_name(&staff, &knowledge, "", terse),
+            "a staff of light [12](oak)"
+        );
+    }
+
+    #[test]
+    fn food_weapons_and_armor() {
+        let knowledge = knowledge();
+        assert_eq!(name(&many(FOOD, 0, 1), &knowledge), "Some food");
+        assert_eq!(name(&many(FOOD, 0, 2), &knowledge), "2 rations of food");
+        assert_eq!(name(&many(FOOD, 1, 1), &knowledge), "A slime-mold");
+
+        let mut mace = many(WEAPON, MACE, 1);
+        assert_eq!(name(&mace, &knowledge), "A mace");
+        mace.hplus = 1;
+        mace.dplus = -2;
+        mace.flags |= IS_KNOW;
+        assert_eq!(name(&mace, &knowledge), "A +1,-2 mace");
+
+        let mut mail = many(ARMOR, RING_MAIL, 1);
+        mail.armor = A_CLASS[RING_MAIL as usize] - 1;
+        assert_eq!(name(&mail, &knowledge), "Ring mail");
+        mail.flags |= IS_KNOW;
+        assert_eq!(name(&mail, &knowledge), "+1 ring mail [protection 4]");
+    }
+}
@@ -0,0 +1,187 @@
+use glam::IVec2;
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::thing::knowledge::Knowledge;
+use crate::thing::object_table::{
+    pick_one, ARM_INFO, A_CLASS, DAGGER, INIT_DAM, NO_LAUNCHER, POT_INFO, RING_INFO, R_ADDDAM,
+    R_ADDHIT, R_ADDSTR, R_AGGR, R_PROTECT, R_TELEPORT, SCR_INFO, THINGS, WEAP_INFO, WS_INFO,
+    WS_LIGHT,
+};
+use crate::thing::{
+    ObjectThing, ARMOR, FOOD, IS_CURSED, IS_MANY, POTION, RING, SCROLL, STICK, WEAPON,
+};
+
+impl ObjectThing {
+    /// Create a blank object of the given type and variant.
+    pub fn new(kind: i32, which: i32) -> Self {
+        Self {
+            kind,
+            pos: IVec2::ZERO,
+            text: None,
+            launch: NO_LAUNCHER,
+            pack_ch: '\0',
+            damage: String::from("0x0"),
+            hurl_dmg: String::from("0x0"),
+            count: 1,
+            which,
+            hplus: 0,
+            dplus: 0,
+            armor: 0,
+            flags: 0,
+            group: 0,
+            label: None,
+        }
+    }
+}
+
+/// Group number of gold piles; missile groups are numbered after it.
+pub const GOLD_GROUP: i32 = 1;
+
+/// Bookkeeping that `new_thing` carries between calls (Rogue's `no_food`
+/// and `group` globals).
+#[derive(Debug, Clone)]
+pub struct ThingCounters {
+    /// Levels filled since the last food turned up; past 3 the next thing is food.
+    pub no_food: i32,
+    /// Last group number handed out to a stack of missiles.
+    pub group: i32,
+}
+
+impl Default for ThingCounters {
+    fn default() -> Self {
+        Self {
+            no_food: 0,
+            group: GOLD_GROUP,
+        }
+    }
+}
+
+impl ThingCounters {
+    fn next_group(&mut self) -> i32 {
+        self.group += 1;
+        self.group
+    }
+}
+
+/// Return a new thing (Rogue's `new_thing`).
+pub fn new_thing<R: Rng + ?Sized>(
+    rng: &mut R,
+    counters: &mut ThingCounters,
+    knowledge: &Knowledge,
+) -> ObjectThing {
+    let kind = if counters.no_food > 3 {
+        2
+    } else {
+        pick_one(&THINGS, rng)
+    };
+
+    match kind {
+        0 => ObjectThing::new(POTION, pick_one(&POT_INFO, rng) as i32),
+        1 => ObjectThing::new(SCROLL, pick_one(&SCR_INFO, rng) as i32),
+        2 => {
+            counters.no_food = 0;
+            // One fruit for every ten rations.
+            ObjectThing::new(FOOD, if rnd(rng, 10) != 0 { 0 } else { 1 })
+        }
+        3 => {
+            let mut cur = init_weapon(pick_one(&WEAP_INFO, rng) as i32, rng, counters);
+            let r = rnd(rng, 100);
+            if r < 10 {
+                cur.flags |= IS_CURSED;
+                cur.hplus -= rnd(rng, 3) + 1;
+            } else if r < 15 {
+                cur.hplus += rnd(rng, 3) + 1;
+            }
+            cur
+        }
+        4 => {
+            let which = pick_one(&ARM_INFO, rng) as i32;
+            let mut cur = ObjectThing::new(ARMOR, which);
+            cur.armor = A_CLASS[which as usize];
+            let r = rnd(rng, 100);
+            if r < 20 {
+                cur.flags |= IS_CURSED;
+                cur.armor += rnd(rng, 3) + 1;
+            } else if r < 28 {
+                cur.armor -= rnd(rng, 3) + 1;
+            }
+            cur
+        }
+        5 => {
+            let which = pick_one(&RING_INFO, rng) as i32;
+            let mut cur = ObjectThing::new(RING, which);
+            match which {
+                R_ADDSTR | R_PROTECT | R_ADDHIT | R_ADDDAM => {
+                    cur.armor = rnd(rng, 3);
+                    if cur.armor == 0 {
+                        cur.armor = -1;
+                        cur.flags |= IS_CURSED;
+                    }
+                }
+                R_AGGR | R_TELEPORT => cur.flags |= IS_CURSED,
+                _ => {}
+            }
+            cur
+        }
+        _ => {
+            let which = pick_one(&WS_INFO, rng) as i32;
+            let staff = knowledge.type_name(STICK, which) == "staff";
+            fix_stick(ObjectThing::new(STICK, which), staff, rng)
+        }
+    }
+}
+
+/// Set up a new weapon of the given type (Rogue's `init_weapon`).
+pub fn init_weapon<R: Rng + ?Sized>(
+    which: i32,
+    rng: &mut R,
+    counters: &mut ThingCounters,
+) -> ObjectThing {
+    let iwp = &INIT_DAM[which as usize];
+    let mut weap = ObjectThing::new(WEAPON, which);
+    weap.damage = iwp.damage.to_string();
+    weap.hurl_dmg = iwp.hurl_dmg.to_string();
+    weap.launch = iwp.launch;
+    weap.flags = iwp.flags;
+    if which == DAGGER {
+        weap.count = rnd(rng, 4) + 2;
+        weap.group = counters.next_group();
+    } else if weap.on(IS_MANY) {
+        weap.count = rnd(rng, 8) + 8;
+        weap.group = counters.next_group();
+    }
+    weap
+}
+
+/// Set up a new stick: its charges and melee damage, which is more for a
+/// staff than a wand (Rogue's `fix_stick`).
+pub fn fix_stick<R: Rng + ?Sized>(mut cur: ObjectThing, staff: bool, rng: &mut R) -> ObjectThing {
+    cur.damage = String::from(if staff { "2x3" } else { "1x1" });
+    cur.hurl_dmg = String::from("1x1");
+    cur.armor = if cur.which == WS_LIGHT {
+        rnd(rng, 10) + 10
+    } else {
+        rnd(rng, 5) + 3
+    };
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+
+    #[test]
+    fn food_turns_up_after_four_levels_without() {
+        let knowledge = Knowledge::default();
+        let mut rng = GameRng::new(7);
+        let mut counters = ThingCounters {
+            no_food: 4,
+            ..ThingCounters::default()
+        };
+        let obj = new_thing(&mut rng, &mut counters, &knowledge);
+        assert_eq!(obj.kind, FOOD);
+        assert_eq!(counters.no_food, 0);
+    }
+}
@@ -0,0 +1,313 @@
+use rand::Rng;
+
+use crate::dice::rnd;
+use crate::terrain::terrain_cell::TerrainCellClass;
+use crate::thing::{
+    AMULET, ARMOR, FOOD, GOLD, IS_MANY, IS_MISL, POTION, RING, SCROLL, STICK, WEAPON,
+};
+
+/// Stuff about objects (Rogue's `struct obj_info`, minus the knowledge fields).
+#[derive(Debug, Clone, Copy)]
+pub struct ObjInfo {
+    pub name: &'static str,
+    /// Probability out of 100 of picking this entry.
+    pub prob: i32,
+    /// Base value in gold, used for scoring.
+    pub worth: i32,
+}
+
+const fn info(name: &'static str, prob: i32, worth: i32) -> ObjInfo {
+    ObjInfo { name, prob, worth }
+}
+
+/* Potion types */
+pub const P_CONFUSE: i32 = 0;
+pub const P_LSD: i32 = 1;
+pub const P_POISON: i32 = 2;
+pub const P_STRENGTH: i32 = 3;
+pub const P_SEEINVIS: i32 = 4;
+pub const P_HEALING: i32 = 5;
+pub const P_MFIND: i32 = 6;
+pub const P_TFIND: i32 = 7;
+pub const P_RAISE: i32 = 8;
+pub const P_XHEAL: i32 = 9;
+pub const P_HASTE: i32 = 10;
+pub const P_RESTORE: i32 = 11;
+pub const P_BLIND: i32 = 12;
+pub const P_LEVIT: i32 = 13;
+pub const MAX_POTIONS: usize = 14;
+
+/* Scroll types */
+pub const S_CONFUSE: i32 = 0;
+pub const S_MAP: i32 = 1;
+pub const S_HOLD: i32 = 2;
+pub const S_SLEEP: i32 = 3;
+pub const S_ARMOR: i32 = 4;
+pub const S_ID_POTION: i32 = 5;
+pub const S_ID_SCROLL: i32 = 6;
+pub const S_ID_WEAPON: i32 = 7;
+pub const S_ID_ARMOR: i32 = 8;
+pub const S_ID_R_OR_S: i32 = 9;
+pub const S_SCARE: i32 = 10;
+pub const S_FDET: i32 = 11;
+pub const S_TELEP: i32 = 12;
+pub const S_ENCH: i32 = 13;
+pub const S_CREATE: i32 = 14;
+pub const S_REMOVE: i32 = 15;
+pub const S_AGGR: i32 = 16;
+pub const S_PROTECT: i32 = 17;
+pub const MAX_SCROLLS: usize = 18;
+
+/* Weapon types */
+pub const MACE: i32 = 0;
+pub const SWORD: i32 = 1;
+pub const BOW: i32 = 2;
+pub const ARROW: i32 = 3;
+pub const DAGGER: i32 = 4;
+pub const TWOSWORD: i32 = 5;
+pub const DART: i32 = 6;
+pub const SHIRAKEN: i32 = 7;
+pub const SPEAR: i32 = 8;
+pub const MAX_WEAPONS: usize = 9;
+pub const FLAME: i32 = MAX_WEAPONS as i32; /* fake weapon for bolts from sticks */
+
+/// `ObjectThing::launch` value for weapons that need no launcher.
+pub const NO_LAUNCHER: i32 = -1;
+
+/* Armor types */
+pub const LEATHER: i32 = 0;
+pub const RING_MAIL: i32 = 1;
+pub const STUDDED_LEATHER: i32 = 2;
+pub const SCALE_MAIL: i32 = 3;
+pub const CHAIN_MAIL: i32 = 4;
+pub const SPLINT_MAIL: i32 = 5;
+pub const BANDED_MAIL: i32 = 6;
+pub const PLATE_MAIL: i32 = 7;
+pub const MAX_ARMORS: usize = 8;
+
+/* Ring types */
+pub const R_PROTECT: i32 = 0;
+pub const R_ADDSTR: i32 = 1;
+pub const R_SUSTSTR: i32 = 2;
+pub const R_SEARCH: i32 = 3;
+pub const R_SEEINVIS: i32 = 4;
+pub const R_NOP: i32 = 5;
+pub const R_AGGR: i32 = 6;
+pub const R_ADDHIT: i32 = 7;
+pub const R_ADDDAM: i32 = 8;
+pub const R_REGEN: i32 = 9;
+pub const R_DIGEST: i32 = 10;
+pub const R_TELEPORT: i32 = 11;
+pub const R_STEALTH: i32 = 12;
+pub const R_SUSTARM: i32 = 13;
+pub const MAX_RINGS: usize = 14;
+
+/* Rod/Wand/Staff types */
+pub const WS_LIGHT: i32 = 0;
+pub const WS_INVIS: i32 = 1;
+pub const WS_ELECT: i32 = 2;
+pub const WS_FIRE: i32 = 3;
+pub const WS_COLD: i32 = 4;
+pub const WS_POLYMORPH: i32 = 5;
+pub const WS_MISSILE: i32 = 6;
+pub const WS_HASTE_M: i32 = 7;
+pub const WS_SLOW_M: i32 = 8;
+pub const WS_DRAIN: i32 = 9;
+pub const WS_NOP: i32 = 10;
+pub const WS_TELAWAY: i32 = 11;
+pub const WS_TELMON: i32 = 12;
+pub const WS_CANCEL: i32 = 13;
+pub const MAX_STICKS: usize = 14;
+
+/// Number of types of things `new_thing` can pick from.
+pub const NUM_THINGS: usize = 7;
+
+/// Relative probabilities of each kind of thing, in `new_thing` order:
+/// potion, scroll, food, weapon, armor, ring, stick.
+pub const THINGS: [ObjInfo; NUM_THINGS] = [
+    info("", 26, 0), // potion
+    info("", 36, 0), // scroll
+    info("", 16, 0), // food
+    info("", 7, 0),  // weapon
+    info("", 7, 0),  // armor
+    info("", 4, 0),  // ring
+    info("", 4, 0),  // stick
+];
+
+pub const POT_INFO: [ObjInfo; MAX_POTIONS] = [
+    info("confusion", 7, 5),
+    info("hallucination", 8, 5),
+    info("poison", 8, 5),
+    info("gain strength", 13, 150),
+    info("see invisible", 3, 100),
+    info("healing", 13, 130),
+    info("monster detection", 6, 130),
+    info("magic detection", 6, 105),
+    info("raise level", 2, 250),
+    info("extra healing", 5, 200),
+    info("haste self", 5, 190),
+    info("restore strength", 13, 130),
+    info("blindness", 5, 5),
+    info("levitation", 6, 75),
+];
+
+pub const SCR_INFO: [ObjInfo; MAX_SCROLLS] = [
+    info("monster confusion", 7, 140),
+    info("magic mapping", 4, 150),
+    info("hold monster", 2, 180),
+    info("sleep", 3, 5),
+    info("enchant armor", 7, 160),
+    info("identify potion", 10, 80),
+    info("identify scroll", 10, 80),
+    info("identify weapon", 6, 80),
+    info("identify armor", 7, 100),
+    info("identify ring, wand or staff", 10, 115),
+    info("scare monster", 3, 200),
+    info("food detection", 2, 60),
+    info("teleportation", 5, 165),
+    info("enchant weapon", 8, 150),
+    info("create monster", 4, 75),
+    info("remove curse", 7, 105),
+    info("aggravate monsters", 3, 20),
+    info("protect armor", 2, 250),
+];
+
+pub const WEAP_INFO: [ObjInfo; MAX_WEAPONS] = [
+    info("mace", 11, 8),
+    info("long sword", 11, 15),
+    info("short bow", 12, 15),
+    info("arrow", 12, 1),
+    info("dagger", 8, 3),
+    info("two handed sword", 10, 75),
+    info("dart", 12, 2),
+    info("shuriken", 12, 5),
+    info("spear", 12, 5),
+];
+
+pub const ARM_INFO: [ObjInfo; MAX_ARMORS] = [
+    info("leather armor", 20, 20),
+    info("ring mail", 15, 25),
+    info("studded leather armor", 15, 20),
+    info("scale mail", 13, 30),
+    info("chain mail", 12, 75),
+    info("splint mail", 10, 80),
+    info("banded mail", 10, 90),
+    info("plate mail", 5, 150),
+];
+
+pub const RING_INFO: [ObjInfo; MAX_RINGS] = [
+    info("protection", 9, 400),
+    info("add strength", 9, 400),
+    info("sustain strength", 5, 280),
+    info("searching", 10, 420),
+    info("see invisible", 10, 310),
+    info("adornment", 1, 10),
+    info("aggravate monster", 10, 10),
+    info("dexterity", 8, 440),
+    info("increase damage", 8, 400),
+    info("regeneration", 4, 460),
+    info("slow digestion", 9, 240),
+    info("teleportation", 5, 30),
+    info("stealth", 7, 470),
+    info("maintain armor", 5, 380),
+];
+
+pub const WS_INFO: [ObjInfo; MAX_STICKS] = [
+    info("light", 12, 250),
+    info("invisibility", 6, 5),
+    info("lightning", 3, 330),
+    info("fire", 3, 330),
+    info("cold", 3, 330),
+    info("polymorph", 15, 310),
+    info("magic missile", 10, 170),
+    info("haste monster", 10, 5),
+    info("slow monster", 11, 350),
+    info("drain life", 9, 300),
+    info("nothing", 1, 5),
+    info("teleport away", 6, 340),
+    info("teleport to", 6, 50),
+    info("cancellation", 5, 280),
+];
+
+/// Base armor class for each armor type (Rogue's `a_class`).
+pub const A_CLASS: [i32; MAX_ARMORS] = [8, 7, 7, 6, 5, 4, 4, 3];
+
+/// Initial damage, thrown damage, launcher and flags for each weapon type
+/// (Rogue's `init_dam`).
+pub struct InitWeapon {
+    pub damage: &'static str,
+    pub hurl_dmg: &'static str,
+    pub launch: i32,
+    pub flags: i32,
+}
+
+#[rustfmt::skip]
+pub const INIT_DAM: [InitWeapon; MAX_WEAPONS] = [
+    InitWeapon { damage: "2x4", hurl_dmg: "1x3", launch: NO_LAUNCHER, flags: 0 }, // Mace
+    InitWeapon { damage: "3x4", hurl_dmg: "1x2", launch: NO_LAUNCHER, flags: 0 }, // Long sword
+    InitWeapon { damage: "1x1", hurl_dmg: "1x1", launch: NO_LAUNCHER, flags: 0 }, // Bow
+    InitWeapon {
+        damage: "1x1",
+        hurl_dmg: "2x3",
+        launch: BOW,
+        flags: IS_MANY | IS_MISL,
+    }, // Arrow
+    InitWeapon { damage: "1x6", hurl_dmg: "1x4", launch: NO_LAUNCHER, flags: IS_MISL }, // Dagger
+    InitWeapon { damage: "4x4", hurl_dmg: "1x2", launch: NO_LAUNCHER, flags: 0 }, // 2h sword
+    InitWeapon {
+        damage: "1x1",
+        hurl_dmg: "1x3",
+        launch: NO_LAUNCHER,
+        flags: IS_MANY | IS_MISL,
+    }, // Dart
+    InitWeapon {
+        damage: "1x2",
+        hurl_dmg: "2x4",
+        launch: NO_LAUNCHER,
+        flags: IS_MANY | IS_MISL,
+    }, // Shuriken
+    InitWeapon { damage: "2x3", hurl_dmg: "1x6", launch: NO_LAUNCHER, flags: IS_MISL }, // Spear
+];
+
+/// Pick an item out of a list of nitems possible objects (Rogue's `pick_one`).
+///
+/// Returns the index of the chosen entry.
+pub fn pick_one<R: Rng + ?Sized>(info: &[ObjInfo], rng: &mut R) -> usize {
+    let mut i = rnd(rng, 100);
+    for (idx, entry) in info.iter().enumerate() {
+        if i < entry.prob {
+            return idx;
+        }
+        i -= entry.prob;
+    }
+    0
+}
+
+/// Get the info table for an object type, if the type has one.
+pub fn info_table(kind: i32) -> Option<&'static [ObjInfo]> {
+    match kind {
+        POTION => Some(&POT_INFO),
+        SCROLL => Some(&SCR_INFO),
+        WEAPON => Some(&WEAP_INFO),
+        ARMOR => Some(&ARM_INFO),
+        RING => Some(&RING_INFO),
+        STICK => Some(&WS_INFO),
+        _ => None,
+    }
+}
+
+/// The terrain cell class an object of the given type shows up as on the map.
+pub fn object_cell_class(kind: i32) -> TerrainCellClass {
+    match kind {
+        POTION => TerrainCellClass::Potion,
+        SCROLL => TerrainCellClass::Scroll,
+        FOOD => TerrainCellClass::Food,
+        WEAPON => TerrainCellClass::Weapon,
+        ARMOR => TerrainCellClass::Armor,
+        RING => TerrainCellClass::Ring,
+        STICK => TerrainCellClass::Stick,
+        GOLD => TerrainCellClass::Gold,
+        AMULET => TerrainCellClass::Amulet,
+        _ => TerrainCellClass::Magic,
+    }
+}
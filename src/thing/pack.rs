@@ -0,0 +1,190 @@
+use crate::thing::{ObjectThing, FOOD, POTION, SCROLL};
+
+/* Most things the hero can carry. */
+pub const MAXPACK: i32 = 23;
+
+/// Whether a type of object stacks one on another in the pack (Rogue's
+/// `ISMULT`). Missiles stack by group instead.
+pub fn is_mult(kind: i32) -> bool {
+    kind == POTION || kind == SCROLL || kind == FOOD
+}
+
+/// The hero's pack (Rogue's `player.t_pack`). Objects of a type are kept
+/// together, each stack under its own letter.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pack {
+    pub items: Vec<ObjectThing>,
+}
+
+impl Pack {
+    /// Pack slots in use (Rogue's `inpack`). Every potion, scroll and food
+    /// takes a slot; a group of missiles takes one.
+    pub fn inpack(&self) -> i32 {
+        self.items
+            .iter()
+            .map(|o| if o.group != 0 { 1 } else { o.count })
+            .sum()
+    }
+
+    /// First letter not used by anything in the pack (Rogue's `pack_char`).
+    pub fn pack_char(&self) -> char {
+        ('a'..='z')
+            .find(|&ch| self.items.iter().all(|o| o.pack_ch != ch))
+            .unwrap_or('z')
+    }
+
+    /// The stack `obj` would join, if any.
+    fn stack_for(&self, obj: &ObjectThing) -> Option<usize> {
+        self.items.iter().position(|op| {
+            op.kind == obj.kind
+                && op.which == obj.which
+                && if obj.group != 0 {
+                    op.group == obj.group
+                } else {
+                    is_mult(obj.kind)
+                }
+        })
+    }
+
+    /// Whether `obj` fits in the pack (Rogue's `pack_room`). Missiles
+    /// joining their group always fit.
+    pub fn room_for(&self, obj: &ObjectThing) -> bool {
+        if obj.group != 0 {
+            return self.stack_for(obj).is_some() || self.inpack() < MAXPACK;
+        }
+        self.inpack() + obj.count <= MAXPACK
+    }
+
+    /// Put an object in the pack, stacking it on its kind where it can,
+    /// and return the letter it is under (Rogue's `add_pack`). The caller
+    /// checks `room_for` first.
+    pub fn add(&mut self, mut obj: ObjectThing) -> char {
+        if let Some(i) = self.stack_for(&obj) {
+            self.items[i].count += obj.count;
+            return self.items[i].pack_ch;
+        }
+        obj.pack_ch = self.pack_char();
+        let ch = obj.pack_ch;
+        // Keep it next to others of its type.
+        match self.items.iter().rposition(|op| op.kind == obj.kind) {
+            Some(i) => self.items.insert(i + 1, obj),
+            None => self.items.push(obj),
+        }
+        ch
+    }
+
+    pub fn get(&self, ch: char) -> Option<&ObjectThing> {
+        self.items.iter().find(|o| o.pack_ch == ch)
+    }
+
+    pub fn get_mut(&mut self, ch: char) -> Option<&mut ObjectThing> {
+        self.items.iter_mut().find(|o| o.pack_ch == ch)
+    }
+
+    /// Take an object out of the pack (Rogue's `leave_pack`). From a stack
+    /// this takes one unless `all` is set; the last one frees its letter.
+    pub fn leave(&mut self, ch: char, all: bool) -> Option<ObjectThing> {
+        let i = self.items.iter().position(|o| o.pack_ch == ch)?;
+        let obj = &mut self.items[i];
+        if obj.count > 1 && !all {
+            obj.count -= 1;
+            let mut nobj = obj.clone();
+            nobj.count = 1;
+            return Some(nobj);
+        }
+        Some(self.items.remove(i))
+    }
+
+    /// Objects in the pack, in order, of one type or of all types.
+    pub fn inventory(&self, kind: Option<i32>) -> impl Iterator<Item = &ObjectThing> {
+        self.items
+            .iter()
+            .filter(move |o| kind.is_none_or(|k| o.kind == k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::GameRng;
+    use crate::thing::new_thing::{init_weapon, ThingCounters};
+    use crate::thing::object_table::{ARROW, MACE};
+    use crate::thing::WEAPON;
+
+    #[test]
+    fn letters_go_to_the_first_free_one() {
+        let mut pack = Pack::default();
+        assert_eq!(pack.add(ObjectThing::new(FOOD, 0)), 'a');
+        assert_eq!(pack.add(ObjectThing::new(WEAPON, MACE)), 'b');
+        assert_eq!(pack.add(ObjectThing::new(POTION, 2)), 'c');
+        pack.leave('b', true);
+        assert_eq!(pack.add(ObjectThing::new(SCROLL, 1)), 'b');
+    }
+
+    #[test]
+    fn like_things_stack_and_keep_together() {
+        let mut pack = Pack::default();
+        pack.add(ObjectThing::new(POTION, 2));
+        pack.add(ObjectThing::new(FOOD, 0));
+        assert_eq!(pack.add(ObjectThing::new(POTION, 2)), 'a');
+        assert_eq!(pack.get('a').map(|o| o.count), Some(2));
+        // A different potion gets its own letter, next to the other one.
+        assert_eq!(pack.add(ObjectThing::new(POTION, 3)), 'c');
+        let kinds: Vec<i32> = pack.items.iter().map(|o| o.kind).collect();
+        assert_eq!(kinds, [POTION, POTION, FOOD]);
+        // Weapons other than missiles never stack.
+        pack.add(ObjectThing::new(WEAPON, MACE));
+        assert_eq!(pack.add(ObjectThing::new(WEAPON, MACE)), 'e');
+        assert_eq!(pack.inpack(), 6);
+    }
+
+    #[test]
+    fn missiles_stack_by_group() {
+        let mut rng = GameRng::new(1);
+        let mut counters = ThingCounters::default();
+        let arrows = init_weapon(ARROW, &mut rng, &mut counters);
+        let more = init_weapon(ARROW, &mut rng, &mut counters);
+        let mut pack = Pack::default();
+        let ch = pack.add(arrows.clone());
+        assert_eq!(pack.add(arrows.clone()), ch);
+        assert_eq!(pack.get(ch).map(|o| o.count), Some(2 * arrows.count));
+        assert_ne!(pack.add(more), ch);
+        assert_eq!(pack.inpack(), 2);
+    }
+
+    #[test]
+    fn room_runs_out_at_maxpack() {
+        let mut pack = Pack::default();
+        let mut potions = ObjectThing::new(POTION, 0);
+        potions.count = MAXPACK - 1;
+        pack.add(potions);
+        assert!(pack.room_for(&ObjectThing::new(SCROLL, 0)));
+        pack.add(ObjectThing::new(SCROLL, 0));
+        assert_eq!(pack.inpack(), MAXPACK);
+        assert!(!pack.room_for(&ObjectThing::new(SCROLL, 0)));
+
+        // A missile joining its group still fits; a new group does not.
+        let mut rng = GameRng::new(1);
+        let mut counters = ThingCounters::default();
+        let arrows = init_weapon(ARROW, &mut rng, &mut counters);
+        assert!(!pack.room_for(&arrows));
+        pack.leave('b', true);
+        pack.add(arrows.clone());
+        assert!(pack.room_for(&arrows));
+    }
+
+    #[test]
+    fn leaving_takes_one_from_a_stack() {
+        let mut pack = Pack::default();
+        let mut potions = ObjectThing::new(POTION, 0);
+        potions.count = 3;
+        pack.add(potions);
+        let one = pack.leave('a', false).unwrap();
+        assert_eq!(one.count, 1);
+        assert_eq!(pack.get('a').map(|o| o.count), Some(2));
+        assert_eq!(pack.leave('a', true).map(|o| o.count), Some(2));
+        assert!(pack.get('a').is_none());
+        assert!(pack.leave('a', true).is_none());
+    }
+}
@@ -0,0 +1,33 @@
+/// Structure describing a fighting being (Rogue's `struct stats`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    /// Strength.
+    pub strength: i32,
+    /// Experience points (for monsters: the award for killing them).
+    pub exp: i32,
+    /// Level of mastery.
+    pub level: i32,
+    /// Armor class (lower is better).
+    pub armor: i32,
+    /// Current hit points.
+    pub hp: i32,
+    /// String describing damage done, e.g. `"1x8/1x8/2x6"`.
+    pub damage: String,
+    /// Max hit points.
+    pub max_hp: i32,
+}
+
+impl Stats {
+    pub fn new(strength: i32, exp: i32, level: i32, armor: i32, hp: i32, damage: &str) -> Self {
+        Self {
+            strength,
+            exp,
+            level,
+            armor,
+            hp,
+            damage: damage.to_string(),
+            max_hp: hp,
+        }
+    }
+}